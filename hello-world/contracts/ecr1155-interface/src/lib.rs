@@ -0,0 +1,68 @@
+#![no_std]
+//! Types and entrypoints of `ecr1155::RealEstateTokenContract` that other
+//! contracts call.
+//!
+//! The contract implements [`RealEstateToken`] and re-exports these types, so
+//! a change to any of them breaks the contract's build rather than drifting
+//! silently from its callers. Callers use the generated
+//! [`RealEstateTokenClient`] and don't need the contract's wasm to build.
+use soroban_sdk::{contractclient, contracttype, Address, Env, String};
+
+/// Stage of a property's life. Every property starts as `Draft`; `Sold` and
+/// `Cancelled` are final.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PropertyStatus {
+    Draft,
+    Fundraising,
+    UnderConstruction,
+    Delivered,
+    Sold,
+    Cancelled,
+}
+
+impl PropertyStatus {
+    pub fn can_transition_to(self, next: PropertyStatus) -> bool {
+        use PropertyStatus::*;
+        matches!(
+            (self, next),
+            (Draft, Fundraising)
+                | (Draft, Cancelled)
+                | (Fundraising, UnderConstruction)
+                | (Fundraising, Cancelled)
+                | (UnderConstruction, Delivered)
+                | (UnderConstruction, Cancelled)
+                | (Delivered, Sold)
+        )
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Property {
+    pub id: u128,
+    pub builder: Address,
+    pub name_property: String,
+    pub ele_quer: i128,
+    pub ele_tem: i128,
+    pub total_supply: i128,
+    /// Cap on `total_supply` for later mints. `None` leaves issuance uncapped.
+    pub max_supply: Option<i128>,
+    pub nome_construtora: String,
+    pub ipfs: String,
+    pub sigla: String,
+    pub status: PropertyStatus,
+    pub status_updated_at: u64,
+}
+
+#[contractclient(name = "RealEstateTokenClient")]
+pub trait RealEstateToken {
+    /// Property `property_id`. Panics with `InvalidProperty` if there is none.
+    fn get_property(env: Env, property_id: u128) -> Property;
+
+    /// Price of one fraction in payment-token base units, rounded up.
+    fn price(env: Env, property: Property) -> i128;
+
+    /// Funding progress in basis points (`10_000` = fully funded), rounded down.
+    fn percentual(env: Env, property: Property) -> u128;
+}
@@ -0,0 +1,155 @@
+use soroban_sdk::{
+    contractimpl, contracttype, panic_with_error, Address, BytesN, Env, String, Vec,
+};
+
+use crate::{
+    events, storage, Error, RealEstateTokenContract, RealEstateTokenContractArgs,
+    RealEstateTokenContractClient, Role,
+};
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BuilderStatus {
+    Active,
+    Suspended,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Builder {
+    pub address: Address,
+    /// Razão social; must match `Property.nome_construtora` on every registration.
+    pub legal_name: String,
+    /// SHA-256 of the builder's CNPJ, so the number itself stays off-chain.
+    pub cnpj_hash: BytesN<32>,
+    pub documents_uri: String,
+    pub status: BuilderStatus,
+    pub registered_at: u64,
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Admin-only. Adds `builder` to the registry as an active builder.
+    pub fn add_builder(
+        env: Env,
+        admin: Address,
+        builder: Address,
+        legal_name: String,
+        cnpj_hash: BytesN<32>,
+        documents_uri: String,
+    ) {
+        rbac::require_role(&env, &admin, Role::Admin);
+
+        if storage::read_builder(&env, &builder).is_some() {
+            panic_with_error!(&env, Error::BuilderExists);
+        }
+
+        let profile = Builder {
+            address: builder.clone(),
+            legal_name: legal_name.clone(),
+            cnpj_hash,
+            documents_uri,
+            status: BuilderStatus::Active,
+            registered_at: env.ledger().timestamp(),
+        };
+        storage::write_builder(&env, &profile);
+
+        let mut builders = storage::read_builders(&env);
+        builders.push_back(builder.clone());
+        storage::write_builders(&env, &builders);
+
+        events::BuilderAdded {
+            builder,
+            legal_name,
+        }
+        .publish(&env);
+    }
+
+    /// Admin-only. Replaces the profile data of an existing builder.
+    pub fn update_builder(
+        env: Env,
+        admin: Address,
+        builder: Address,
+        legal_name: String,
+        cnpj_hash: BytesN<32>,
+        documents_uri: String,
+    ) {
+        rbac::require_role(&env, &admin, Role::Admin);
+
+        let mut profile = Self::get_builder(env.clone(), builder.clone());
+        profile.legal_name = legal_name.clone();
+        profile.cnpj_hash = cnpj_hash;
+        profile.documents_uri = documents_uri;
+        storage::write_builder(&env, &profile);
+
+        events::BuilderUpdated {
+            builder,
+            legal_name,
+        }
+        .publish(&env);
+    }
+
+    /// Admin-only. A suspended builder keeps its properties but cannot register new ones.
+    pub fn suspend_builder(env: Env, admin: Address, builder: Address) {
+        Self::_set_builder_status(&env, admin, builder, BuilderStatus::Suspended);
+    }
+
+    pub fn reinstate_builder(env: Env, admin: Address, builder: Address) {
+        Self::_set_builder_status(&env, admin, builder, BuilderStatus::Active);
+    }
+
+    /// Admin-only. Drops `builder` from the registry. Properties it already
+    /// registered are left untouched.
+    pub fn remove_builder(env: Env, admin: Address, builder: Address) {
+        rbac::require_role(&env, &admin, Role::Admin);
+
+        if storage::read_builder(&env, &builder).is_none() {
+            panic_with_error!(&env, Error::BuilderNotFound);
+        }
+        storage::remove_builder(&env, &builder);
+
+        let mut builders = storage::read_builders(&env);
+        if let Some(index) = builders.first_index_of(&builder) {
+            builders.remove(index);
+        }
+        storage::write_builders(&env, &builders);
+
+        events::BuilderRemoved { builder }.publish(&env);
+    }
+
+    pub fn get_builder(env: Env, builder: Address) -> Builder {
+        storage::read_builder(&env, &builder)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::BuilderNotFound))
+    }
+
+    pub fn is_active_builder(env: Env, builder: Address) -> bool {
+        matches!(
+            storage::read_builder(&env, &builder),
+            Some(Builder {
+                status: BuilderStatus::Active,
+                ..
+            })
+        )
+    }
+
+    /// Addresses of every registered builder, in registration order.
+    pub fn list_builders(env: Env) -> Vec<Address> {
+        storage::read_builders(&env)
+    }
+
+    /// Ids of every property registered by `builder`.
+    pub fn builder_properties(env: Env, builder: Address) -> Vec<u128> {
+        storage::read_builder_properties(&env, &builder)
+    }
+
+    fn _set_builder_status(env: &Env, admin: Address, builder: Address, status: BuilderStatus) {
+        rbac::require_role(env, &admin, Role::Admin);
+
+        let mut profile = storage::read_builder(env, &builder)
+            .unwrap_or_else(|| panic_with_error!(env, Error::BuilderNotFound));
+        profile.status = status;
+        storage::write_builder(env, &profile);
+
+        events::BuilderStatusChanged { builder, status }.publish(env);
+    }
+}
@@ -0,0 +1,128 @@
+//! Paged, filtered listing of registered properties.
+//!
+//! Property ids are sequential, so the catalogue is walked in id order with the
+//! id to resume from as the cursor. Each call reads at most [`MAX_SCAN`]
+//! properties however selective the filter is; a page can therefore come back
+//! short or empty with a `next_cursor` still set, and the caller keeps paging
+//! until it is `None`. Filtering by builder only walks that builder's own list.
+use soroban_sdk::{contractimpl, contracttype, Address, Env, String, Vec};
+
+use crate::{
+    holders, math, storage, Property, PropertyStatus, RealEstateTokenContract,
+    RealEstateTokenContractArgs, RealEstateTokenContractClient,
+};
+
+/// Most properties read by one `list_properties` call.
+pub const MAX_SCAN: u32 = 200;
+
+/// Criteria a listed property must meet. `None` fields match everything.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PropertyFilter {
+    pub builder: Option<Address>,
+    /// Lifecycle statuses to include. Empty includes every status.
+    pub statuses: Vec<PropertyStatus>,
+    /// Inclusive bounds on funding progress, in basis points.
+    pub min_funding_bps: Option<u32>,
+    pub max_funding_bps: Option<u32>,
+    pub sigla: Option<String>,
+}
+
+impl PropertyFilter {
+    /// Filter that matches every property.
+    pub fn any(env: &Env) -> Self {
+        PropertyFilter {
+            builder: None,
+            statuses: Vec::new(env),
+            min_funding_bps: None,
+            max_funding_bps: None,
+            sigla: None,
+        }
+    }
+
+    fn matches(&self, env: &Env, property: &Property) -> bool {
+        if matches!(&self.builder, Some(builder) if *builder != property.builder) {
+            return false;
+        }
+        if !self.statuses.is_empty() && !self.statuses.contains(property.status) {
+            return false;
+        }
+        if matches!(&self.sigla, Some(sigla) if *sigla != property.sigla) {
+            return false;
+        }
+        if self.min_funding_bps.is_some() || self.max_funding_bps.is_some() {
+            let funding = math::funding_bps(env, property);
+            if matches!(self.min_funding_bps, Some(min) if funding < min as i128) {
+                return false;
+            }
+            if matches!(self.max_funding_bps, Some(max) if funding > max as i128) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PropertyPage {
+    pub properties: Vec<Property>,
+    /// Id to pass as `cursor` for the next page, `None` once the catalogue is
+    /// exhausted.
+    pub next_cursor: Option<u128>,
+}
+
+fn scan(
+    env: &Env,
+    ids: impl Iterator<Item = u128>,
+    limit: u32,
+    filter: &PropertyFilter,
+) -> PropertyPage {
+    let mut properties = Vec::new(env);
+    for (scanned, id) in ids.enumerate() {
+        if properties.len() == limit || scanned as u32 == MAX_SCAN {
+            return PropertyPage {
+                properties,
+                next_cursor: Some(id),
+            };
+        }
+        if let Some(property) = storage::read_property(env, id) {
+            if filter.matches(env, &property) {
+                properties.push_back(property);
+            }
+        }
+    }
+    PropertyPage {
+        properties,
+        next_cursor: None,
+    }
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Properties matching `filter`, in id order, starting at id `cursor`.
+    /// Returns one page, sized by `limit` as [`holders::page_size`] describes.
+    pub fn list_properties(
+        env: Env,
+        cursor: u128,
+        limit: u32,
+        filter: PropertyFilter,
+    ) -> PropertyPage {
+        let limit = holders::page_size(limit);
+        match &filter.builder {
+            Some(builder) => {
+                let ids = storage::read_builder_properties(&env, builder);
+                scan(&env, ids.iter().filter(|id| *id >= cursor), limit, &filter)
+            }
+            None => {
+                let next_id = storage::read_next_property_id(&env);
+                scan(&env, cursor.max(1)..next_id, limit, &filter)
+            }
+        }
+    }
+
+    /// Number of properties ever registered.
+    pub fn property_count(env: Env) -> u128 {
+        storage::read_next_property_id(&env) - 1
+    }
+}
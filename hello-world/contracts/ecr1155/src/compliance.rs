@@ -0,0 +1,184 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Symbol, Vec};
+
+use crate::{
+    events, storage, Error, RealEstateTokenContract, RealEstateTokenContractArgs,
+    RealEstateTokenContractClient, Role,
+};
+
+/// Investor classes as defined by CVM.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InvestorCategory {
+    Retail,
+    Qualified,
+    Professional,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Identity {
+    /// ISO 3166-1 alpha-2 code of the investor's jurisdiction, e.g. `BR`.
+    pub jurisdiction: Symbol,
+    pub category: InvestorCategory,
+    pub verified_at: u64,
+}
+
+/// Restrictions applied to every transfer of one property. Properties without
+/// rules of their own use [`TransferRules::unrestricted`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferRules {
+    /// Both sender and recipient must have a registered identity.
+    pub kyc_required: bool,
+    /// Jurisdictions a recipient may belong to. Empty allows any jurisdiction.
+    pub allowed_jurisdictions: Vec<Symbol>,
+    /// Maximum number of distinct holders. Zero means no cap.
+    pub max_holders: u32,
+}
+
+impl TransferRules {
+    pub fn unrestricted(env: &Env) -> Self {
+        TransferRules {
+            kyc_required: false,
+            allowed_jurisdictions: Vec::new(env),
+            max_holders: 0,
+        }
+    }
+}
+
+/// Applies the property's [`TransferRules`] to a transfer of `amount` fractions,
+/// given the balances of both parties before the transfer.
+pub(crate) fn check_transfer_rules(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    id: u128,
+    amount: i128,
+    from_balance: i128,
+    to_balance: i128,
+) -> Result<(), Error> {
+    let rules = storage::read_transfer_rules(env, id);
+
+    if rules.kyc_required && storage::read_identity(env, from).is_none() {
+        return Err(Error::SenderNotVerified);
+    }
+    check_recipient(env, &rules, to)?;
+
+    if rules.max_holders > 0 && from != to && to_balance == 0 {
+        let leaving = if from_balance == amount { 1 } else { 0 };
+        let holders = storage::read_holder_count(env, id) + 1 - leaving;
+        if holders > rules.max_holders {
+            return Err(Error::HolderLimitReached);
+        }
+    }
+
+    Ok(())
+}
+
+/// Rules a mint of `id` to `to` must satisfy: the recipient side of
+/// [`check_transfer_rules`].
+pub(crate) fn check_mint_rules(
+    env: &Env,
+    to: &Address,
+    id: u128,
+    to_balance: i128,
+) -> Result<(), Error> {
+    let rules = storage::read_transfer_rules(env, id);
+    check_recipient(env, &rules, to)?;
+
+    if rules.max_holders > 0
+        && to_balance == 0
+        && storage::read_holder_count(env, id) + 1 > rules.max_holders
+    {
+        return Err(Error::HolderLimitReached);
+    }
+    Ok(())
+}
+
+pub(crate) fn check_recipient(env: &Env, rules: &TransferRules, to: &Address) -> Result<(), Error> {
+    let recipient = storage::read_identity(env, to);
+    if rules.kyc_required && recipient.is_none() {
+        return Err(Error::RecipientNotVerified);
+    }
+
+    if !rules.allowed_jurisdictions.is_empty() {
+        match recipient {
+            Some(identity) if rules.allowed_jurisdictions.contains(&identity.jurisdiction) => {}
+            _ => return Err(Error::JurisdictionNotAllowed),
+        }
+    }
+    Ok(())
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Compliance-only. Marks `account` as KYC-verified, replacing any previous identity.
+    pub fn register_identity(
+        env: Env,
+        officer: Address,
+        account: Address,
+        jurisdiction: Symbol,
+        category: InvestorCategory,
+    ) {
+        rbac::require_role(&env, &officer, Role::Compliance);
+
+        let identity = Identity {
+            jurisdiction,
+            category,
+            verified_at: env.ledger().timestamp(),
+        };
+        storage::write_identity(&env, &account, &identity);
+
+        events::IdentityRegistered { account, identity }.publish(&env);
+    }
+
+    /// Compliance-only. `account` keeps its balances but fails every KYC check.
+    pub fn remove_identity(env: Env, officer: Address, account: Address) {
+        rbac::require_role(&env, &officer, Role::Compliance);
+
+        if storage::read_identity(&env, &account).is_none() {
+            panic_with_error!(&env, Error::IdentityNotFound);
+        }
+        storage::remove_identity(&env, &account);
+
+        events::IdentityRemoved { account, officer }.publish(&env);
+    }
+
+    pub fn get_identity(env: Env, account: Address) -> Identity {
+        storage::read_identity(&env, &account)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::IdentityNotFound))
+    }
+
+    pub fn is_verified(env: Env, account: Address) -> bool {
+        storage::read_identity(&env, &account).is_some()
+    }
+
+    /// Compliance-only. Replaces the transfer rules of property `id`.
+    pub fn set_transfer_rules(env: Env, officer: Address, id: u128, rules: TransferRules) {
+        rbac::require_role(&env, &officer, Role::Compliance);
+
+        if storage::read_property(&env, id).is_none() {
+            panic_with_error!(&env, Error::InvalidProperty);
+        }
+        storage::write_transfer_rules(&env, id, &rules);
+
+        events::TransferRulesSet {
+            property_id: id,
+            rules,
+        }
+        .publish(&env);
+    }
+
+    pub fn get_transfer_rules(env: Env, id: u128) -> TransferRules {
+        storage::read_transfer_rules(&env, id)
+    }
+
+    /// Dry-runs a transfer. Returns `0` when it would succeed, otherwise the
+    /// contract error code the transfer would fail with.
+    pub fn can_transfer(env: Env, from: Address, to: Address, id: u128, amount: i128) -> u32 {
+        match Self::_check_transfer(&env, &from, &to, id, amount) {
+            Ok(()) => 0,
+            Err(e) => soroban_sdk::Error::from(e).get_code(),
+        }
+    }
+}
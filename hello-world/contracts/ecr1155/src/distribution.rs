@@ -0,0 +1,149 @@
+//! Pro-rata income distribution (rent, for instance) to fraction holders.
+//!
+//! Each property keeps a cumulative "reward per fraction" accumulator that grows
+//! on every deposit. A holder's entitlement is their balance times the growth of
+//! the accumulator since they were last settled, so deposits and claims cost the
+//! same no matter how many holders a property has. Every balance change must
+//! call [`settle`] for the affected holder first.
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, token, Address, Env, Vec};
+
+use crate::{
+    events,
+    math::{self, Rounding},
+    pause, storage, Error, RealEstateTokenContract, RealEstateTokenContractArgs,
+    RealEstateTokenContractClient, Role,
+};
+
+/// Extra precision carried by the accumulator so small deposits over large
+/// supplies are not rounded away.
+const REWARD_PRECISION: i128 = 1_000_000_000_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HolderRewards {
+    /// Accumulator value the holder was last settled at.
+    pub paid_per_fraction: i128,
+    /// Settled but unclaimed rewards.
+    pub pending: i128,
+}
+
+fn accrued(env: &Env, holder: &Address, id: u128) -> HolderRewards {
+    let mut rewards = storage::read_holder_rewards(env, holder, id);
+    let per_fraction = storage::read_reward_per_fraction(env, id);
+    if per_fraction != rewards.paid_per_fraction {
+        let balance = storage::read_balance(env, holder, id);
+        rewards.pending += math::mul_div(
+            env,
+            balance,
+            per_fraction - rewards.paid_per_fraction,
+            REWARD_PRECISION,
+            Rounding::Down,
+        );
+        rewards.paid_per_fraction = per_fraction;
+    }
+    rewards
+}
+
+/// Books everything `holder` earned on `id` at its current balance.
+pub(crate) fn settle(env: &Env, holder: &Address, id: u128) {
+    let rewards = accrued(env, holder, id);
+    if rewards != storage::read_holder_rewards(env, holder, id) {
+        storage::write_holder_rewards(env, holder, id, &rewards);
+    }
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Deposits `amount` of `token` to be shared among the holders of `id` in
+    /// proportion to their balances. Callable by the property's builder or an
+    /// `Operator`. A property pays out in a single token, fixed by its first deposit.
+    pub fn deposit_income(env: Env, depositor: Address, id: u128, token: Address, amount: i128) {
+        depositor.require_auth();
+        pause::require_property_not_paused(&env, id);
+
+        let property = storage::read_property(&env, id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::InvalidProperty));
+        if depositor != property.builder && !rbac::has_role(&env, Role::Operator, &depositor) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InsufficientBalance);
+        }
+        match storage::read_reward_token(&env, id) {
+            Some(existing) if existing != token => {
+                panic_with_error!(&env, Error::RewardTokenMismatch)
+            }
+            Some(_) => {}
+            None => storage::write_reward_token(&env, id, &token),
+        }
+
+        token::Client::new(&env, &token).transfer(
+            &depositor,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let increment = math::mul_div(
+            &env,
+            amount,
+            REWARD_PRECISION,
+            property.total_supply,
+            Rounding::Down,
+        );
+        let per_fraction = storage::read_reward_per_fraction(&env, id) + increment;
+        storage::write_reward_per_fraction(&env, id, per_fraction);
+
+        events::IncomeDeposited {
+            property_id: id,
+            depositor,
+            token,
+            amount,
+            reward_per_fraction: per_fraction,
+        }
+        .publish(&env);
+    }
+
+    /// Rewards `holder` can currently claim on property `id`.
+    pub fn claimable(env: Env, holder: Address, id: u128) -> i128 {
+        accrued(&env, &holder, id).pending
+    }
+
+    /// Pays out everything `holder` has accrued on each of `ids`. Returns the
+    /// amount paid per property, in the same order.
+    pub fn claim(env: Env, holder: Address, ids: Vec<u128>) -> Vec<i128> {
+        holder.require_auth();
+
+        let mut paid = Vec::new(&env);
+        for id in ids.iter() {
+            pause::require_property_not_paused(&env, id);
+
+            let mut rewards = accrued(&env, &holder, id);
+            let amount = rewards.pending;
+            if amount > 0 {
+                let token = storage::read_reward_token(&env, id)
+                    .unwrap_or_else(|| panic_with_error!(&env, Error::InvalidProperty));
+                rewards.pending = 0;
+                storage::write_holder_rewards(&env, &holder, id, &rewards);
+
+                token::Client::new(&env, &token).transfer(
+                    &env.current_contract_address(),
+                    &holder,
+                    &amount,
+                );
+                events::IncomeClaimed {
+                    property_id: id,
+                    holder: holder.clone(),
+                    token,
+                    amount,
+                }
+                .publish(&env);
+            }
+            paid.push_back(amount);
+        }
+        paid
+    }
+
+    pub fn income_token(env: Env, id: u128) -> Option<Address> {
+        storage::read_reward_token(&env, id)
+    }
+}
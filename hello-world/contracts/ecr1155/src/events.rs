@@ -0,0 +1,767 @@
+//! Typed events for every state change of the contract.
+//!
+//! Each event is published with the topics `(name, EVENT_VERSION)` followed by
+//! the key shown on its `publish` method (a property id or an address), and its
+//! struct as the data, so consumers decode named fields instead of positional
+//! tuples. Any change to a struct's fields bumps [`EVENT_VERSION`].
+use soroban_sdk::{contracttype, Address, BytesN, Env, IntoVal, String, Symbol, Val, Vec};
+
+use crate::{
+    BuilderStatus, GovernanceParams, HoldingLimits, Identity, InvestorCategory, PropertyStatus,
+    ProposalStatus, RecoveryAction, TimelockAction, TransferRules, VestingSchedule,
+};
+
+/// Schema version, published as the second topic of every event.
+pub const EVENT_VERSION: u32 = 1;
+
+fn publish<K, D>(env: &Env, name: &str, key: K, data: D)
+where
+    K: IntoVal<Env, Val>,
+    D: IntoVal<Env, Val>,
+{
+    env.events()
+        .publish((Symbol::new(env, name), EVENT_VERSION, key), data);
+}
+
+fn publish_global<D: IntoVal<Env, Val>>(env: &Env, name: &str, data: D) {
+    env.events()
+        .publish((Symbol::new(env, name), EVENT_VERSION), data);
+}
+
+/// Legacy instance storage was moved to per-key entries.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StorageMigrated {
+    /// Number of properties migrated.
+    pub properties: u32,
+    /// Number of balance entries migrated.
+    pub balances: u32,
+}
+
+impl StorageMigrated {
+    pub(crate) fn publish(self, env: &Env) {
+        publish_global(env, "storage_migrated", self);
+    }
+}
+
+/// A builder was approved to register properties.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BuilderAdded {
+    pub builder: Address,
+    /// Registered legal name (razão social).
+    pub legal_name: String,
+}
+
+impl BuilderAdded {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "builder_added", self.builder.clone(), self);
+    }
+}
+
+/// A builder's profile data was replaced.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BuilderUpdated {
+    pub builder: Address,
+    /// Legal name after the update.
+    pub legal_name: String,
+}
+
+impl BuilderUpdated {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "builder_updated", self.builder.clone(), self);
+    }
+}
+
+/// A builder was suspended or reinstated.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BuilderStatusChanged {
+    pub builder: Address,
+    /// Status after the change.
+    pub status: BuilderStatus,
+}
+
+impl BuilderStatusChanged {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "builder_status_changed", self.builder.clone(), self);
+    }
+}
+
+/// A builder was removed from the registry.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BuilderRemoved {
+    pub builder: Address,
+}
+
+impl BuilderRemoved {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "builder_removed", self.builder.clone(), self);
+    }
+}
+
+/// A property was registered and its whole initial supply issued to its builder.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PropertyRegistered {
+    pub property_id: u128,
+    pub builder: Address,
+    /// Property name as registered.
+    pub name: String,
+    /// Fractions issued to the builder.
+    pub total_supply: i128,
+}
+
+impl PropertyRegistered {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "property_registered", self.property_id, self);
+    }
+}
+
+/// A property moved to a new lifecycle status.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PropertyStatusChanged {
+    pub property_id: u128,
+    pub previous: PropertyStatus,
+    pub status: PropertyStatus,
+    /// Ledger timestamp of the change.
+    pub changed_at: u64,
+}
+
+impl PropertyStatusChanged {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "property_status_changed", self.property_id, self);
+    }
+}
+
+/// The supply cap of a property was set or removed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaxSupplySet {
+    pub property_id: u128,
+    /// New cap, `None` if issuance is now uncapped.
+    pub max_supply: Option<i128>,
+}
+
+impl MaxSupplySet {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "max_supply_set", self.property_id, self);
+    }
+}
+
+/// Fractions moved between two holders.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PropertyTransferred {
+    pub property_id: u128,
+    pub from: Address,
+    pub to: Address,
+    /// Number of fractions moved.
+    pub amount: i128,
+}
+
+impl PropertyTransferred {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "property_transferred", self.property_id, self);
+    }
+}
+
+/// New fractions were issued.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FractionsMinted {
+    pub property_id: u128,
+    pub to: Address,
+    /// Number of fractions issued.
+    pub amount: i128,
+    /// Supply of the property after the mint.
+    pub total_supply: i128,
+}
+
+impl FractionsMinted {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "fractions_minted", self.property_id, self);
+    }
+}
+
+/// Fractions were destroyed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FractionsBurned {
+    pub property_id: u128,
+    pub holder: Address,
+    /// Number of fractions destroyed.
+    pub amount: i128,
+    /// Supply of the property after the burn.
+    pub total_supply: i128,
+}
+
+impl FractionsBurned {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "fractions_burned", self.property_id, self);
+    }
+}
+
+/// An operator was allowed or forbidden to move all of an owner's fractions.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalForAll {
+    pub owner: Address,
+    pub operator: Address,
+    pub approved: bool,
+}
+
+impl ApprovalForAll {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "approval_for_all", self.owner.clone(), self);
+    }
+}
+
+/// A spender's allowance on one property was set.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PropertyApproved {
+    pub property_id: u128,
+    pub owner: Address,
+    pub spender: Address,
+    /// Fractions the spender may move, replacing any previous allowance.
+    pub amount: i128,
+    /// Last ledger the allowance is valid for.
+    pub expiration_ledger: u32,
+}
+
+impl PropertyApproved {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "property_approved", self.property_id, self);
+    }
+}
+
+/// The whole contract was halted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Paused {
+    pub pauser: Address,
+}
+
+impl Paused {
+    pub(crate) fn publish(self, env: &Env) {
+        publish_global(env, "paused", self);
+    }
+}
+
+/// The whole contract was resumed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Unpaused {
+    pub pauser: Address,
+}
+
+impl Unpaused {
+    pub(crate) fn publish(self, env: &Env) {
+        publish_global(env, "unpaused", self);
+    }
+}
+
+/// Transfers of one property were halted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PropertyPaused {
+    pub property_id: u128,
+    pub pauser: Address,
+}
+
+impl PropertyPaused {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "property_paused", self.property_id, self);
+    }
+}
+
+/// Transfers of one property were resumed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PropertyUnpaused {
+    pub property_id: u128,
+    pub pauser: Address,
+}
+
+impl PropertyUnpaused {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "property_unpaused", self.property_id, self);
+    }
+}
+
+/// An investor identity was registered or replaced.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IdentityRegistered {
+    pub account: Address,
+    pub identity: Identity,
+}
+
+impl IdentityRegistered {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "identity_registered", self.account.clone(), self);
+    }
+}
+
+/// An investor identity was removed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IdentityRemoved {
+    pub account: Address,
+    /// Compliance officer who removed it.
+    pub officer: Address,
+}
+
+impl IdentityRemoved {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "identity_removed", self.account.clone(), self);
+    }
+}
+
+/// The transfer rules of a property were replaced.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferRulesSet {
+    pub property_id: u128,
+    pub rules: TransferRules,
+}
+
+impl TransferRulesSet {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "transfer_rules_set", self.property_id, self);
+    }
+}
+
+/// A payment token was accepted or rejected for primary sales.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentTokenSet {
+    pub token: Address,
+    pub accepted: bool,
+}
+
+impl PaymentTokenSet {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "payment_token_set", self.token.clone(), self);
+    }
+}
+
+/// The primary-sale treasury changed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasurySet {
+    pub treasury: Address,
+}
+
+impl TreasurySet {
+    pub(crate) fn publish(self, env: &Env) {
+        publish_global(env, "treasury_set", self);
+    }
+}
+
+/// An investor bought fractions from a builder's inventory.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FractionsPurchased {
+    pub property_id: u128,
+    pub investor: Address,
+    /// Number of fractions bought.
+    pub amount: i128,
+    pub payment_token: Address,
+    /// Amount paid, in `payment_token` base units.
+    pub cost: i128,
+}
+
+impl FractionsPurchased {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "fractions_purchased", self.property_id, self);
+    }
+}
+
+/// Income was deposited for the holders of a property.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IncomeDeposited {
+    pub property_id: u128,
+    pub depositor: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// Cumulative reward per fraction after the deposit, scaled by 1e12.
+    pub reward_per_fraction: i128,
+}
+
+impl IncomeDeposited {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "income_deposited", self.property_id, self);
+    }
+}
+
+/// A holder claimed their income from a property.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IncomeClaimed {
+    pub property_id: u128,
+    pub holder: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+impl IncomeClaimed {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "income_claimed", self.property_id, self);
+    }
+}
+
+/// A snapshot of a property's balances was taken.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SnapshotTaken {
+    pub property_id: u128,
+    pub snapshot_id: u32,
+    /// Ledger timestamp of the snapshot.
+    pub taken_at: u64,
+}
+
+impl SnapshotTaken {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "snapshot_taken", self.property_id, self);
+    }
+}
+
+/// The governance thresholds of a property changed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceParamsSet {
+    pub property_id: u128,
+    pub params: GovernanceParams,
+}
+
+impl GovernanceParamsSet {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "governance_params_set", self.property_id, self);
+    }
+}
+
+/// A proposal was opened on a property.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalCreated {
+    pub property_id: u128,
+    pub proposal_id: u64,
+    pub proposer: Address,
+    /// Snapshot the voting weights are read from.
+    pub snapshot_id: u32,
+    /// Voting window, in ledger timestamps; `end` is exclusive.
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ProposalCreated {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "proposal_created", self.property_id, self);
+    }
+}
+
+/// A holder voted on a proposal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteCast {
+    pub property_id: u128,
+    pub proposal_id: u64,
+    pub voter: Address,
+    /// Index into the proposal's options.
+    pub option: u32,
+    /// Voter's balance at the proposal snapshot.
+    pub weight: i128,
+}
+
+impl VoteCast {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "vote_cast", self.property_id, self);
+    }
+}
+
+/// A proposal was closed and its outcome recorded.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalFinalized {
+    pub property_id: u128,
+    pub proposal_id: u64,
+    pub status: ProposalStatus,
+    /// Index of the winning option, `None` if the proposal was rejected.
+    pub winning_option: Option<u32>,
+    /// Total voting weight cast.
+    pub votes_cast: i128,
+}
+
+impl ProposalFinalized {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "proposal_finalized", self.property_id, self);
+    }
+}
+
+/// Sale proceeds were deposited for a sold property.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SaleProceedsDeposited {
+    pub property_id: u128,
+    pub depositor: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// Proceeds still owed to holders after the deposit.
+    pub remaining: i128,
+}
+
+impl SaleProceedsDeposited {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "sale_proceeds_deposited", self.property_id, self);
+    }
+}
+
+/// A holder burned fractions of a sold property for their share of the proceeds.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FractionsRedeemed {
+    pub property_id: u128,
+    pub holder: Address,
+    /// Number of fractions burned.
+    pub amount: i128,
+    pub token: Address,
+    /// Proceeds paid, in `token` base units.
+    pub payout: i128,
+}
+
+impl FractionsRedeemed {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "fractions_redeemed", self.property_id, self);
+    }
+}
+
+/// A contract opted in or out of `on_fraction_received` calls.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FractionReceiverSet {
+    pub receiver: Address,
+    pub enabled: bool,
+}
+
+impl FractionReceiverSet {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "fraction_receiver_set", self.receiver.clone(), self);
+    }
+}
+
+/// A vesting schedule was put on a holder's fractions.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingCreated {
+    pub property_id: u128,
+    pub holder: Address,
+    pub schedule: VestingSchedule,
+}
+
+impl VestingCreated {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "vesting_created", self.property_id, self);
+    }
+}
+
+/// The holding caps of a property were replaced.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HoldingLimitsSet {
+    pub property_id: u128,
+    pub limits: HoldingLimits,
+}
+
+impl HoldingLimitsSet {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "holding_limits_set", self.property_id, self);
+    }
+}
+
+/// The annual investment ceiling of an investor category changed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnnualCapSet {
+    pub category: InvestorCategory,
+    pub cap: i128,
+}
+
+impl AnnualCapSet {
+    pub(crate) fn publish(self, env: &Env) {
+        publish_global(env, "annual_cap_set", self);
+    }
+}
+
+/// The delay applied to forced transfers and account recoveries changed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryDelaySet {
+    pub delay: u64,
+}
+
+impl RecoveryDelaySet {
+    pub(crate) fn publish(self, env: &Env) {
+        publish_global(env, "recovery_delay_set", self);
+    }
+}
+
+/// A forced transfer or account recovery was queued behind the recovery delay.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryQueued {
+    pub request_id: u64,
+    pub operator: Address,
+    pub action: RecoveryAction,
+    pub reason_hash: BytesN<32>,
+    pub eta: u64,
+}
+
+impl RecoveryQueued {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "recovery_queued", self.request_id, self);
+    }
+}
+
+/// A queued forced transfer or account recovery was dropped.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryCancelled {
+    pub request_id: u64,
+    pub operator: Address,
+}
+
+impl RecoveryCancelled {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "recovery_cancelled", self.request_id, self);
+    }
+}
+
+/// Fractions were moved by a compliance officer without the holder's signature.
+/// Also published as a `property_transferred` event.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ForcedTransfer {
+    pub property_id: u128,
+    pub operator: Address,
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+    pub reason_hash: BytesN<32>,
+}
+
+impl ForcedTransfer {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "forced_transfer", self.property_id, self);
+    }
+}
+
+/// Every holding of an account was moved to a new one.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountRecovered {
+    pub operator: Address,
+    pub old: Address,
+    pub new: Address,
+    /// Properties whose balances moved.
+    pub properties: Vec<u128>,
+    pub reason_hash: BytesN<32>,
+}
+
+impl AccountRecovered {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "account_recovered", self.old.clone(), self);
+    }
+}
+
+/// Compliance froze every transfer to or from an account.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddressFrozen {
+    pub account: Address,
+    pub officer: Address,
+}
+
+impl AddressFrozen {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "address_frozen", self.account.clone(), self);
+    }
+}
+
+/// Compliance lifted an account freeze.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddressUnfrozen {
+    pub account: Address,
+    pub officer: Address,
+}
+
+impl AddressUnfrozen {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "address_unfrozen", self.account.clone(), self);
+    }
+}
+
+/// Compliance set the frozen part of a holder's balance. Zero lifts it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartialFreezeSet {
+    pub property_id: u128,
+    pub holder: Address,
+    pub officer: Address,
+    pub amount: i128,
+}
+
+impl PartialFreezeSet {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "partial_freeze_set", self.property_id, self);
+    }
+}
+
+/// The contract's code was replaced.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractUpgraded {
+    pub wasm_hash: BytesN<32>,
+}
+
+impl ContractUpgraded {
+    pub(crate) fn publish(self, env: &Env) {
+        publish_global(env, "contract_upgraded", self);
+    }
+}
+
+/// Stored entries were migrated between schema versions.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SchemaMigrated {
+    pub from: u32,
+    pub to: u32,
+}
+
+impl SchemaMigrated {
+    pub(crate) fn publish(self, env: &Env) {
+        publish_global(env, "schema_migrated", self);
+    }
+}
+
+/// A privileged action was queued behind the timelock. The `rbac`
+/// `operation_scheduled` event carries only its id.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActionScheduled {
+    pub operation_id: BytesN<32>,
+    pub action: TimelockAction,
+    pub eta: u64,
+}
+
+impl ActionScheduled {
+    pub(crate) fn publish(self, env: &Env) {
+        publish(env, "action_scheduled", self.operation_id.clone(), self);
+    }
+}
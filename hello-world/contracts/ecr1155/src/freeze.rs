@@ -0,0 +1,118 @@
+//! Compliance freezes on suspicious accounts.
+//!
+//! A frozen account can neither send, receive nor redeem fractions of any
+//! property. A partial freeze instead pins part of one holder's balance of one property,
+//! leaving the rest free to move. Forced transfers and account recovery are
+//! not subject to either, as they are how a freeze is usually resolved.
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env};
+
+use crate::{
+    events, storage, Error, RealEstateTokenContract, RealEstateTokenContractArgs,
+    RealEstateTokenContractClient, Role,
+};
+
+fn partially_frozen(env: &Env, holder: &Address, id: u128, balance: i128) -> i128 {
+    storage::read_frozen_amount(env, holder, id).min(balance)
+}
+
+/// Rejects a frozen account receiving fractions.
+pub(crate) fn check_recipient(env: &Env, to: &Address) -> Result<(), Error> {
+    if storage::read_frozen(env, to) {
+        return Err(Error::AccountFrozen);
+    }
+    Ok(())
+}
+
+/// Rejects taking `amount` out of `holder`'s `balance` of `id` if the account
+/// is frozen or doing so would dip into the frozen part of `balance`.
+pub(crate) fn check_holder(
+    env: &Env,
+    holder: &Address,
+    id: u128,
+    amount: i128,
+    balance: i128,
+) -> Result<(), Error> {
+    if storage::read_frozen(env, holder) {
+        return Err(Error::AccountFrozen);
+    }
+    if balance - amount < partially_frozen(env, holder, id, balance) {
+        return Err(Error::FractionsFrozen);
+    }
+    Ok(())
+}
+
+/// Rejects moving `amount` out of `from`'s `balance` of `id` to `to` if either
+/// account is frozen or the move would dip into the frozen part of `balance`.
+pub(crate) fn check_transfer(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    id: u128,
+    amount: i128,
+    balance: i128,
+) -> Result<(), Error> {
+    if storage::read_frozen(env, from) {
+        return Err(Error::AccountFrozen);
+    }
+    check_recipient(env, to)?;
+    check_holder(env, from, id, amount, balance)
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Compliance-only. Blocks every transfer to or from `account`.
+    pub fn freeze_address(env: Env, officer: Address, account: Address) {
+        rbac::require_role(&env, &officer, Role::Compliance);
+        storage::write_frozen(&env, &account, true);
+
+        events::AddressFrozen { account, officer }.publish(&env);
+    }
+
+    /// Compliance-only. Lifts a freeze set by `freeze_address`. Partial freezes
+    /// are left in place.
+    pub fn unfreeze_address(env: Env, officer: Address, account: Address) {
+        rbac::require_role(&env, &officer, Role::Compliance);
+        storage::write_frozen(&env, &account, false);
+
+        events::AddressUnfrozen { account, officer }.publish(&env);
+    }
+
+    pub fn is_frozen(env: Env, account: Address) -> bool {
+        storage::read_frozen(&env, &account)
+    }
+
+    /// Compliance-only. Pins `amount` of `holder`'s fractions of `id`,
+    /// replacing any previous partial freeze. Zero lifts it. The amount may
+    /// exceed the current balance, in which case incoming fractions are
+    /// frozen too.
+    pub fn freeze_partial(env: Env, officer: Address, holder: Address, id: u128, amount: i128) {
+        rbac::require_role(&env, &officer, Role::Compliance);
+
+        if storage::read_property(&env, id).is_none() {
+            panic_with_error!(&env, Error::InvalidProperty);
+        }
+        if amount < 0 {
+            panic_with_error!(&env, Error::InsufficientBalance);
+        }
+        storage::write_frozen_amount(&env, &holder, id, amount);
+
+        events::PartialFreezeSet {
+            property_id: id,
+            holder,
+            officer,
+            amount,
+        }
+        .publish(&env);
+    }
+
+    /// Fractions of `id` that `holder` cannot currently move because of a
+    /// freeze: the whole balance if the account is frozen.
+    pub fn frozen_balance(env: Env, holder: Address, id: u128) -> i128 {
+        let balance = storage::read_balance(&env, &holder, id);
+        if storage::read_frozen(&env, &holder) {
+            balance
+        } else {
+            partially_frozen(&env, &holder, id, balance)
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! Holder voting on decisions about a single property: selling it, replacing
+//! its manager, approving major repairs.
+//!
+//! Creating a proposal takes a snapshot of the property and every holder votes
+//! with their balance at that snapshot, so fractions bought or moved while a
+//! vote is open carry no weight. Once the window closes anyone can finalize the
+//! proposal. It passes when turnout reaches the quorum and the leading option
+//! gets more than the approval threshold of the votes cast, both in basis
+//! points. A passing proposal whose first option wins applies its action.
+use soroban_sdk::{
+    contractimpl, contracttype, panic_with_error, Address, BytesN, Env, Symbol, Vec,
+};
+
+use crate::{
+    events, lifecycle,
+    math::{self, Rounding, BPS},
+    pause, snapshots, storage, Error, PropertyStatus, RealEstateTokenContract,
+    RealEstateTokenContractArgs, RealEstateTokenContractClient, Role,
+};
+
+pub const MAX_OPTIONS: u32 = 10;
+
+/// Thresholds applied to new proposals on a property. Proposals keep the
+/// values they were created with.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceParams {
+    /// Share of the snapshot supply that must vote.
+    pub quorum_bps: u32,
+    /// Share of the votes cast the leading option must exceed.
+    pub approval_bps: u32,
+}
+
+impl Default for GovernanceParams {
+    fn default() -> Self {
+        GovernanceParams {
+            quorum_bps: 2_000,
+            approval_bps: 5_000,
+        }
+    }
+}
+
+/// What a proposal does when its first option wins.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProposalAction {
+    /// Only the outcome is recorded; acting on it happens off-chain.
+    Record,
+    /// Moves the property to the given lifecycle status, e.g. `Sold`.
+    SetStatus(PropertyStatus),
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProposalStatus {
+    Active,
+    Passed,
+    Rejected,
+    Executed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub id: u64,
+    pub property_id: u128,
+    pub proposer: Address,
+    /// Hash of the full proposal text, published off-chain.
+    pub description_hash: BytesN<32>,
+    pub options: Vec<Symbol>,
+    pub action: ProposalAction,
+    pub snapshot_id: u32,
+    pub start: u64,
+    pub end: u64,
+    pub quorum_bps: u32,
+    pub approval_bps: u32,
+    /// Voting weight per option, in the order of `options`.
+    pub tally: Vec<i128>,
+    pub status: ProposalStatus,
+    pub winning_option: Option<u32>,
+}
+
+fn read_proposal(env: &Env, proposal_id: u64) -> Proposal {
+    storage::read_proposal(env, proposal_id)
+        .unwrap_or_else(|| panic_with_error!(env, Error::ProposalNotFound))
+}
+
+/// Index of the option with strictly the most votes, if any.
+fn leading_option(tally: &Vec<i128>) -> Option<(u32, i128)> {
+    let mut leader: Option<(u32, i128)> = None;
+    let mut tied = false;
+    for (index, votes) in tally.iter().enumerate() {
+        match leader {
+            Some((_, best)) if votes < best => {}
+            Some((_, best)) if votes == best => tied = true,
+            _ => {
+                leader = Some((index as u32, votes));
+                tied = false;
+            }
+        }
+    }
+    if tied {
+        None
+    } else {
+        leader
+    }
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Admin-only. Sets the quorum and approval thresholds for new proposals
+    /// on `property_id`.
+    pub fn set_governance_params(
+        env: Env,
+        admin: Address,
+        property_id: u128,
+        params: GovernanceParams,
+    ) {
+        rbac::require_role(&env, &admin, Role::Admin);
+        if storage::read_property(&env, property_id).is_none() {
+            panic_with_error!(&env, Error::InvalidProperty);
+        }
+        if params.quorum_bps as i128 > BPS || params.approval_bps as i128 >= BPS {
+            panic_with_error!(&env, Error::InvalidProposal);
+        }
+        storage::write_governance_params(&env, property_id, &params);
+        events::GovernanceParamsSet {
+            property_id,
+            params,
+        }
+        .publish(&env);
+    }
+
+    pub fn governance_params(env: Env, property_id: u128) -> GovernanceParams {
+        storage::read_governance_params(&env, property_id)
+    }
+
+    /// Opens a vote on `property_id` between `start` and `end` (ledger
+    /// timestamps). Callable by any holder of the property, its builder or an
+    /// `Operator`. Voting weight is fixed by a snapshot taken now.
+    pub fn propose(
+        env: Env,
+        proposer: Address,
+        property_id: u128,
+        description_hash: BytesN<32>,
+        options: Vec<Symbol>,
+        action: ProposalAction,
+        start: u64,
+        end: u64,
+    ) -> u64 {
+        proposer.require_auth();
+        pause::require_property_not_paused(&env, property_id);
+
+        let property = storage::read_property(&env, property_id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::InvalidProperty));
+        if proposer != property.builder
+            && storage::read_balance(&env, &proposer, property_id) == 0
+            && !rbac::has_role(&env, Role::Operator, &proposer)
+        {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        if options.len() < 2 || options.len() > MAX_OPTIONS {
+            panic_with_error!(&env, Error::InvalidProposal);
+        }
+        if start < env.ledger().timestamp() || end <= start {
+            panic_with_error!(&env, Error::InvalidProposal);
+        }
+        if let ProposalAction::SetStatus(status) = action {
+            if !property.status.can_transition_to(status) {
+                panic_with_error!(&env, Error::InvalidStatusTransition);
+            }
+        }
+
+        let params = storage::read_governance_params(&env, property_id);
+        let mut tally = Vec::new(&env);
+        for _ in 0..options.len() {
+            tally.push_back(0i128);
+        }
+
+        let id = storage::read_next_proposal_id(&env);
+        let proposal = Proposal {
+            id,
+            property_id,
+            proposer: proposer.clone(),
+            description_hash,
+            options,
+            action,
+            snapshot_id: snapshots::take(&env, property_id),
+            start,
+            end,
+            quorum_bps: params.quorum_bps,
+            approval_bps: params.approval_bps,
+            tally,
+            status: ProposalStatus::Active,
+            winning_option: None,
+        };
+        storage::write_proposal(&env, &proposal);
+        storage::write_next_proposal_id(&env, id + 1);
+        storage::extend_instance(&env);
+
+        events::ProposalCreated {
+            property_id,
+            proposal_id: id,
+            proposer,
+            snapshot_id: proposal.snapshot_id,
+            start,
+            end,
+        }
+        .publish(&env);
+
+        id
+    }
+
+    /// Casts all of `voter`'s snapshot balance for `option`. Returns the
+    /// weight counted.
+    pub fn vote(env: Env, voter: Address, proposal_id: u64, option: u32) -> i128 {
+        voter.require_auth();
+
+        let mut proposal = read_proposal(&env, proposal_id);
+        pause::require_property_not_paused(&env, proposal.property_id);
+        let now = env.ledger().timestamp();
+        if proposal.status != ProposalStatus::Active || now < proposal.start || now >= proposal.end
+        {
+            panic_with_error!(&env, Error::VotingClosed);
+        }
+        if option >= proposal.options.len() {
+            panic_with_error!(&env, Error::InvalidProposal);
+        }
+        if storage::read_vote(&env, proposal_id, &voter).is_some() {
+            panic_with_error!(&env, Error::AlreadyVoted);
+        }
+
+        let weight = Self::balance_of_at(
+            env.clone(),
+            voter.clone(),
+            proposal.property_id,
+            proposal.snapshot_id,
+        );
+        if weight <= 0 {
+            panic_with_error!(&env, Error::NoVotingPower);
+        }
+
+        let votes = proposal.tally.get_unchecked(option) + weight;
+        proposal.tally.set(option, votes);
+        storage::write_proposal(&env, &proposal);
+        storage::write_vote(&env, proposal_id, &voter, option);
+
+        events::VoteCast {
+            property_id: proposal.property_id,
+            proposal_id,
+            voter,
+            option,
+            weight,
+        }
+        .publish(&env);
+
+        weight
+    }
+
+    /// Closes a proposal once its window has ended and records the outcome,
+    /// applying its action if the first option won. Callable by anyone. A
+    /// status change that is no longer allowed by the time the vote ends
+    /// leaves the proposal `Passed` but not `Executed`.
+    pub fn finalize_proposal(env: Env, proposal_id: u64) -> ProposalStatus {
+        let mut proposal = read_proposal(&env, proposal_id);
+        pause::require_property_not_paused(&env, proposal.property_id);
+        if proposal.status != ProposalStatus::Active {
+            panic_with_error!(&env, Error::VotingClosed);
+        }
+        if env.ledger().timestamp() < proposal.end {
+            panic_with_error!(&env, Error::VotingNotEnded);
+        }
+
+        let supply = Self::total_supply_at(env.clone(), proposal.property_id, proposal.snapshot_id);
+        let cast: i128 = proposal.tally.iter().sum();
+        let quorum = math::mul_div(&env, supply, proposal.quorum_bps as i128, BPS, Rounding::Up);
+        let approval = math::mul_div(
+            &env,
+            cast,
+            proposal.approval_bps as i128,
+            BPS,
+            Rounding::Down,
+        );
+
+        proposal.status = ProposalStatus::Rejected;
+        if cast > 0 && cast >= quorum {
+            if let Some((option, votes)) = leading_option(&proposal.tally) {
+                if votes > approval {
+                    proposal.status = ProposalStatus::Passed;
+                    proposal.winning_option = Some(option);
+                }
+            }
+        }
+
+        if proposal.winning_option == Some(0) {
+            if let ProposalAction::SetStatus(status) = proposal.action {
+                let mut property = storage::read_property(&env, proposal.property_id)
+                    .unwrap_or_else(|| panic_with_error!(&env, Error::InvalidProperty));
+                if property.status.can_transition_to(status) {
+                    lifecycle::transition(&env, &mut property, status);
+                    proposal.status = ProposalStatus::Executed;
+                }
+            }
+        }
+        storage::write_proposal(&env, &proposal);
+
+        events::ProposalFinalized {
+            property_id: proposal.property_id,
+            proposal_id,
+            status: proposal.status,
+            winning_option: proposal.winning_option,
+            votes_cast: cast,
+        }
+        .publish(&env);
+
+        proposal.status
+    }
+
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Proposal {
+        read_proposal(&env, proposal_id)
+    }
+
+    /// Option `voter` chose on `proposal_id`, if they voted.
+    pub fn get_vote(env: Env, proposal_id: u64, voter: Address) -> Option<u32> {
+        storage::read_vote(&env, proposal_id, &voter)
+    }
+}
@@ -0,0 +1,107 @@
+//! Enumerable indexes of who holds each property and what each address holds.
+//!
+//! Both are dense, zero-based position lists kept in step with balances, so
+//! they can be paged through with a numeric cursor. Removing an entry moves the
+//! last one into its slot, so a listing taken while balances change may skip or
+//! repeat an entry.
+use soroban_sdk::{contractimpl, Address, Env, Vec};
+
+use crate::{
+    storage, RealEstateTokenContract, RealEstateTokenContractArgs, RealEstateTokenContractClient,
+};
+
+/// Most entries a single page returns, whatever `limit` asks for.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Page size for a requested `limit`, shared by every paginated read: zero
+/// asks for a full page, and anything larger than [`MAX_PAGE_SIZE`] is capped
+/// to it.
+pub(crate) fn page_size(limit: u32) -> u32 {
+    if limit == 0 {
+        MAX_PAGE_SIZE
+    } else {
+        limit.min(MAX_PAGE_SIZE)
+    }
+}
+
+fn add(env: &Env, holder: &Address, id: u128) {
+    let holders = storage::read_holder_count(env, id);
+    storage::write_holder_at(env, id, holders, holder);
+    storage::write_holder_index(env, id, holder, holders);
+    storage::write_holder_count(env, id, holders + 1);
+
+    let owned = storage::read_portfolio_count(env, holder);
+    storage::write_portfolio_at(env, holder, owned, id);
+    storage::write_portfolio_index(env, holder, id, owned);
+    storage::write_portfolio_count(env, holder, owned + 1);
+}
+
+fn remove(env: &Env, holder: &Address, id: u128) {
+    if let Some(index) = storage::read_holder_index(env, id, holder) {
+        let last = storage::read_holder_count(env, id) - 1;
+        if index != last {
+            let moved = storage::read_holder_at(env, id, last).unwrap();
+            storage::write_holder_at(env, id, index, &moved);
+            storage::write_holder_index(env, id, &moved, index);
+        }
+        storage::remove_holder_at(env, id, last);
+        storage::remove_holder_index(env, id, holder);
+        storage::write_holder_count(env, id, last);
+    }
+
+    if let Some(index) = storage::read_portfolio_index(env, holder, id) {
+        let last = storage::read_portfolio_count(env, holder) - 1;
+        if index != last {
+            let moved = storage::read_portfolio_at(env, holder, last).unwrap();
+            storage::write_portfolio_at(env, holder, index, moved);
+            storage::write_portfolio_index(env, holder, moved, index);
+        }
+        storage::remove_portfolio_at(env, holder, last);
+        storage::remove_portfolio_index(env, holder, id);
+        storage::write_portfolio_count(env, holder, last);
+    }
+}
+
+/// Keeps both indexes in line with `holder`'s balance of `id` going from
+/// `before` to `after`. Must accompany every balance write.
+pub(crate) fn update(env: &Env, holder: &Address, id: u128, before: i128, after: i128) {
+    if before == 0 && after > 0 {
+        add(env, holder, id);
+    } else if before > 0 && after == 0 {
+        remove(env, holder, id);
+    }
+}
+
+fn page_end(cursor: u32, limit: u32, len: u32) -> u32 {
+    cursor.saturating_add(page_size(limit)).min(len)
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Holders of `property_id` from position `cursor` on, one page sized by
+    /// `limit` as [`page_size`] describes. The next page starts at `cursor`
+    /// plus the number returned.
+    pub fn holders_of(env: Env, property_id: u128, cursor: u32, limit: u32) -> Vec<Address> {
+        let end = page_end(cursor, limit, storage::read_holder_count(&env, property_id));
+        let mut page = Vec::new(&env);
+        for index in cursor..end {
+            page.push_back(storage::read_holder_at(&env, property_id, index).unwrap());
+        }
+        page
+    }
+
+    /// Properties `holder` has a balance of, paged like [`Self::holders_of`].
+    pub fn properties_of(env: Env, holder: Address, cursor: u32, limit: u32) -> Vec<u128> {
+        let end = page_end(cursor, limit, storage::read_portfolio_count(&env, &holder));
+        let mut page = Vec::new(&env);
+        for index in cursor..end {
+            page.push_back(storage::read_portfolio_at(&env, &holder, index).unwrap());
+        }
+        page
+    }
+
+    /// Number of distinct addresses holding fractions of `id`.
+    pub fn holder_count(env: Env, id: u128) -> u32 {
+        storage::read_holder_count(&env, id)
+    }
+}
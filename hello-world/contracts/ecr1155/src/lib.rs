@@ -1,8 +1,52 @@
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, panic_with_error, Address, Env, Map, String,
+    contract, contractimpl, contracttype, panic_with_error, Address, Env, Map, String, Vec,
 };
 
+mod builders;
+mod catalog;
+mod compliance;
+mod distribution;
+pub mod events;
+mod freeze;
+mod governance;
+mod holders;
+mod lifecycle;
+mod limits;
+mod math;
+mod pause;
+mod receiver;
+mod recovery;
+mod redemption;
+mod sale;
+mod snapshots;
+mod storage;
+mod test;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+mod timelock;
+mod upgrade;
+mod vesting;
+
+pub use builders::{Builder, BuilderStatus};
+pub use catalog::{PropertyFilter, PropertyPage};
+pub use compliance::{Identity, InvestorCategory, TransferRules};
+pub use distribution::HolderRewards;
+pub use ecr1155_interface::{Property, PropertyStatus, RealEstateToken, RealEstateTokenClient};
+pub use governance::{GovernanceParams, Proposal, ProposalAction, ProposalStatus};
+pub use limits::HoldingLimits;
+pub use math::PurchaseQuote;
+pub use rbac::{timelock::RbacAction, Role};
+pub use receiver::{FractionReceiver, FractionReceiverClient};
+pub use recovery::{PendingRecovery, RecoveryAction};
+pub use redemption::SaleProceeds;
+pub use snapshots::Checkpoint;
+pub use storage::DataKey;
+pub use timelock::TimelockAction;
+pub use upgrade::SCHEMA_VERSION;
+pub use vesting::VestingSchedule;
+
 #[contract]
 pub struct RealEstateTokenContract;
 
@@ -12,6 +56,50 @@ pub enum Error {
     InsufficientBalance,
     PropertyExists,
     InvalidProperty,
+    LengthMismatch,
+    InsufficientAllowance,
+    InvalidExpiration,
+    AlreadyMigrated,
+    BuilderExists,
+    BuilderNotFound,
+    BuilderNotActive,
+    BuilderNameMismatch,
+    ContractPaused,
+    PropertyPaused,
+    SenderNotVerified,
+    RecipientNotVerified,
+    JurisdictionNotAllowed,
+    HolderLimitReached,
+    IdentityNotFound,
+    InvalidStatusTransition,
+    InvalidPropertyStatus,
+    PaymentTokenNotAccepted,
+    MaxCostExceeded,
+    ArithmeticError,
+    RewardTokenMismatch,
+    InvalidSnapshot,
+    ProposalNotFound,
+    InvalidProposal,
+    VotingClosed,
+    AlreadyVoted,
+    VotingNotEnded,
+    NoVotingPower,
+    ProceedsNotDeposited,
+    ProceedsTokenMismatch,
+    SupplyCapExceeded,
+    ReceiverRejected,
+    InvalidVesting,
+    FractionsLocked,
+    HoldingLimitExceeded,
+    ConcentrationLimitExceeded,
+    AnnualCapExceeded,
+    InvalidLimits,
+    RecoveryNotFound,
+    RecoveryNotReady,
+    InvalidRecovery,
+    AccountFrozen,
+    FractionsFrozen,
+    MigrationPending,
 }
 
 impl From<Error> for soroban_sdk::Error {
@@ -21,38 +109,212 @@ impl From<Error> for soroban_sdk::Error {
             Error::InsufficientBalance => soroban_sdk::Error::from_contract_error(1002),
             Error::PropertyExists => soroban_sdk::Error::from_contract_error(1003),
             Error::InvalidProperty => soroban_sdk::Error::from_contract_error(1004),
+            Error::LengthMismatch => soroban_sdk::Error::from_contract_error(1005),
+            Error::InsufficientAllowance => soroban_sdk::Error::from_contract_error(1006),
+            Error::InvalidExpiration => soroban_sdk::Error::from_contract_error(1007),
+            Error::AlreadyMigrated => soroban_sdk::Error::from_contract_error(1008),
+            Error::BuilderExists => soroban_sdk::Error::from_contract_error(1009),
+            Error::BuilderNotFound => soroban_sdk::Error::from_contract_error(1010),
+            Error::BuilderNotActive => soroban_sdk::Error::from_contract_error(1011),
+            Error::BuilderNameMismatch => soroban_sdk::Error::from_contract_error(1012),
+            Error::ContractPaused => soroban_sdk::Error::from_contract_error(1013),
+            Error::PropertyPaused => soroban_sdk::Error::from_contract_error(1014),
+            Error::SenderNotVerified => soroban_sdk::Error::from_contract_error(1015),
+            Error::RecipientNotVerified => soroban_sdk::Error::from_contract_error(1016),
+            Error::JurisdictionNotAllowed => soroban_sdk::Error::from_contract_error(1017),
+            Error::HolderLimitReached => soroban_sdk::Error::from_contract_error(1018),
+            Error::IdentityNotFound => soroban_sdk::Error::from_contract_error(1019),
+            Error::InvalidStatusTransition => soroban_sdk::Error::from_contract_error(1020),
+            Error::InvalidPropertyStatus => soroban_sdk::Error::from_contract_error(1021),
+            Error::PaymentTokenNotAccepted => soroban_sdk::Error::from_contract_error(1022),
+            Error::MaxCostExceeded => soroban_sdk::Error::from_contract_error(1023),
+            Error::ArithmeticError => soroban_sdk::Error::from_contract_error(1024),
+            Error::RewardTokenMismatch => soroban_sdk::Error::from_contract_error(1025),
+            Error::InvalidSnapshot => soroban_sdk::Error::from_contract_error(1026),
+            Error::ProposalNotFound => soroban_sdk::Error::from_contract_error(1027),
+            Error::InvalidProposal => soroban_sdk::Error::from_contract_error(1028),
+            Error::VotingClosed => soroban_sdk::Error::from_contract_error(1029),
+            Error::AlreadyVoted => soroban_sdk::Error::from_contract_error(1030),
+            Error::VotingNotEnded => soroban_sdk::Error::from_contract_error(1031),
+            Error::NoVotingPower => soroban_sdk::Error::from_contract_error(1032),
+            Error::ProceedsNotDeposited => soroban_sdk::Error::from_contract_error(1033),
+            Error::ProceedsTokenMismatch => soroban_sdk::Error::from_contract_error(1034),
+            Error::SupplyCapExceeded => soroban_sdk::Error::from_contract_error(1035),
+            Error::ReceiverRejected => soroban_sdk::Error::from_contract_error(1036),
+            Error::InvalidVesting => soroban_sdk::Error::from_contract_error(1037),
+            Error::FractionsLocked => soroban_sdk::Error::from_contract_error(1038),
+            Error::HoldingLimitExceeded => soroban_sdk::Error::from_contract_error(1039),
+            Error::ConcentrationLimitExceeded => soroban_sdk::Error::from_contract_error(1040),
+            Error::AnnualCapExceeded => soroban_sdk::Error::from_contract_error(1041),
+            Error::InvalidLimits => soroban_sdk::Error::from_contract_error(1042),
+            Error::RecoveryNotFound => soroban_sdk::Error::from_contract_error(1043),
+            Error::RecoveryNotReady => soroban_sdk::Error::from_contract_error(1044),
+            Error::InvalidRecovery => soroban_sdk::Error::from_contract_error(1045),
+            Error::AccountFrozen => soroban_sdk::Error::from_contract_error(1046),
+            Error::FractionsFrozen => soroban_sdk::Error::from_contract_error(1047),
+            Error::MigrationPending => soroban_sdk::Error::from_contract_error(1048),
         }
     }
 }
 
+/// Most entries `migrate_legacy_storage` or `migrate` rewrites per call.
+pub const MIGRATION_BATCH_SIZE: u32 = 100;
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Property {
-    pub id: u128,
-    pub builder: Address,
-    pub name_property: String,
-    pub ele_quer: i128,
-    pub ele_tem: i128,
-    pub total_supply: i128,
-    pub nome_construtora: String,
-    pub ipfs: String, 
-    pub sigla: String,
+pub struct Allowance {
+    pub amount: i128,
+    pub expiration_ledger: u32,
 }
 
 #[contractimpl]
 impl RealEstateTokenContract {
     pub fn initialize(env: Env, admin: Address) {
-        env.storage().instance().set(&"admin", &admin);
-        env.storage().instance().set(&"next_property_id", &1u128);
+        // A legacy deployment has no rbac admin yet, but is initialized.
+        if storage::has_legacy_storage(&env) {
+            panic_with_error!(&env, Error::MigrationPending);
+        }
+        rbac::initialize(&env, &admin);
+        storage::write_next_property_id(&env, 1);
+        storage::write_schema_version(&env, SCHEMA_VERSION);
+        storage::extend_instance(&env);
+    }
+
+    /// Moves a pre-`DataKey` deployment off the legacy instance maps
+    /// (`"properties"`, `"balances"`, `"approvals"`, `"allowances"`) into
+    /// per-key persistent entries. Callable only by the legacy admin.
+    ///
+    /// The entries of all four maps, in that order, are migrated `limit` at a
+    /// time starting at position `cursor`, so a large deployment fits in
+    /// several transactions. Returns the cursor of the next batch, or `None`
+    /// once everything has moved and the legacy maps are gone. Until then the
+    /// contract rejects every call that the pause would, with
+    /// `MigrationPending`, so no balance changes under a pending batch.
+    pub fn migrate_legacy_storage(env: Env, cursor: u32, limit: u32) -> Option<u32> {
+        let instance = env.storage().instance();
+        let admin: Address = instance
+            .get(&"admin")
+            .unwrap_or_else(|| panic_with_error!(&env, Error::AlreadyMigrated));
+        admin.require_auth();
+
+        if rbac::member_count(&env, Role::Admin) == 0 {
+            let next_id: u128 = instance.get(&"next_property_id").unwrap_or(1);
+            rbac::initialize(&env, &admin);
+            storage::write_next_property_id(&env, next_id);
+        }
+
+        let properties: Map<u128, storage::LegacyProperty> =
+            instance.get(&"properties").unwrap_or(Map::new(&env));
+        let balances: Map<(Address, u128), i128> =
+            instance.get(&"balances").unwrap_or(Map::new(&env));
+        let approvals: Map<(Address, Address), bool> =
+            instance.get(&"approvals").unwrap_or(Map::new(&env));
+        let allowances: Map<(Address, Address, u128), Allowance> =
+            instance.get(&"allowances").unwrap_or(Map::new(&env));
+
+        let limit = if limit == 0 {
+            MIGRATION_BATCH_SIZE
+        } else {
+            limit.min(MIGRATION_BATCH_SIZE)
+        };
+        let total = properties.len() + balances.len() + approvals.len() + allowances.len();
+        let end = cursor.saturating_add(limit).min(total);
+        let (property_values, balance_keys, approval_keys, allowance_keys) = (
+            properties.values(),
+            balances.keys(),
+            approvals.keys(),
+            allowances.keys(),
+        );
+
+        for position in cursor..end {
+            let mut index = position;
+            if index < properties.len() {
+                let property = property_values.get_unchecked(index);
+                let mut ids = storage::read_builder_properties(&env, &property.builder);
+                if !ids.contains(property.id) {
+                    ids.push_back(property.id);
+                    storage::write_builder_properties(&env, &property.builder, &ids);
+                }
+                storage::write_property(&env, &property.into_property(&env));
+                continue;
+            }
+            index -= properties.len();
+            if index < balances.len() {
+                let (owner, id) = balance_keys.get_unchecked(index);
+                let balance = balances.get_unchecked((owner.clone(), id));
+                let before = storage::read_balance(&env, &owner, id);
+                storage::write_balance(&env, &owner, id, balance);
+                holders::update(&env, &owner, id, before, balance);
+                continue;
+            }
+            index -= balances.len();
+            if index < approvals.len() {
+                let (owner, operator) = approval_keys.get_unchecked(index);
+                let approved = approvals.get_unchecked((owner.clone(), operator.clone()));
+                storage::write_approval(&env, &owner, &operator, approved);
+                continue;
+            }
+            index -= approvals.len();
+            let (owner, spender, id) = allowance_keys.get_unchecked(index);
+            let allowance = allowances.get_unchecked((owner.clone(), spender.clone(), id));
+            if allowance.expiration_ledger >= env.ledger().sequence() {
+                storage::write_allowance(&env, &owner, &spender, id, &allowance);
+            }
+        }
+        storage::extend_instance(&env);
+        if end < total {
+            return Some(end);
+        }
+
+        instance.remove(&"admin");
+        instance.remove(&"next_property_id");
+        instance.remove(&"properties");
+        instance.remove(&"balances");
+        instance.remove(&"approvals");
+        instance.remove(&"allowances");
+        storage::write_schema_version(&env, SCHEMA_VERSION);
+
+        events::StorageMigrated {
+            properties: properties.len(),
+            balances: balances.len(),
+        }
+        .publish(&env);
+        None
     }
 
-    fn get_properties_storage(env: &Env) -> Map<u128, Property> {
-        env.storage()
-            .instance()
-            .get(&"properties")
-            .unwrap_or(Map::new(env))
+    /// Admin-only. Timelocked. Gives `role` to `account`.
+    pub fn grant_role(env: Env, admin: Address, role: Role, account: Address) {
+        rbac::grant_role(&env, &admin, role, &account);
     }
 
+    /// Admin-only. Timelocked. Takes `role` away from `account`.
+    pub fn revoke_role(env: Env, admin: Address, role: Role, account: Address) {
+        rbac::revoke_role(&env, &admin, role, &account);
+    }
+
+    pub fn renounce_role(env: Env, account: Address, role: Role) {
+        rbac::renounce_role(&env, &account, role);
+    }
+
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        rbac::has_role(&env, role, &account)
+    }
+
+    /// Admin-only. Offers `admin`'s role to `new_admin`, who must accept it.
+    /// The offer only becomes acceptable after the timelock's minimum delay.
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) {
+        rbac::propose_admin(&env, &admin, &new_admin);
+    }
+
+    /// Completes a handover: `new_admin` becomes an admin in place of the
+    /// account that proposed it.
+    pub fn accept_admin(env: Env, new_admin: Address) {
+        rbac::accept_admin(&env, &new_admin);
+    }
+
+    pub fn pending_admin(env: Env) -> Option<Address> {
+        rbac::pending_admin(&env)
+    }
 
     pub fn register_property(
         env: Env,
@@ -63,62 +325,107 @@ impl RealEstateTokenContract {
         total_supply: i128,
         nome_construtora: String,
         ipfs: String,
-        sigla: String
-
+        sigla: String,
     ) -> u128 {
         builder.require_auth();
-        Self::_verify_builder(&env, &builder);
+        pause::require_not_paused(&env);
+        Self::_verify_builder(&env, &builder, &nome_construtora);
+        if ele_quer <= 0 || ele_tem < 0 || total_supply <= 0 {
+            panic_with_error!(&env, Error::InvalidProperty);
+        }
 
-        let next_id: u128 = env
-            .storage()
-            .instance()
-            .get(&"next_property_id")
-            .unwrap();
+        let next_id = storage::read_next_property_id(&env);
 
         let property = Property {
             id: next_id,
             builder: builder.clone(),
-            name: property_name.clone(),
+            name_property: property_name.clone(),
             ele_quer,
             ele_tem,
             total_supply,
+            max_supply: None,
             nome_construtora,
             ipfs,
-            sigla
+            sigla,
+            status: PropertyStatus::Draft,
+            status_updated_at: env.ledger().timestamp(),
         };
 
-        let mut properties = Self::get_properties_storage(&env);
-        properties.set(next_id, property);
-        env.storage().instance().set(&"properties", &properties);
-
-        let mut balances: Map<(Address, u128), i128> = env
-            .storage()
-            .instance()
-            .get(&"balances")
-            .unwrap_or(Map::new(&env));
-        balances.set((builder.clone(), next_id), total_supply);
-        env.storage().instance().set(&"balances", &balances);
-
-        env.storage()
-            .instance()
-            .set(&"next_property_id", &(next_id + 1));
-
-        env.events().publish(
-            ("property_registered", next_id),
-            (builder, property_name, total_supply),
-        );
+        snapshots::update_supply(&env, next_id);
+        snapshots::update_balance(&env, &builder, next_id);
+        storage::write_property(&env, &property);
+        storage::write_balance(&env, &builder, next_id, total_supply);
+        holders::update(&env, &builder, next_id, 0, total_supply);
+        let mut builder_properties = storage::read_builder_properties(&env, &builder);
+        builder_properties.push_back(next_id);
+        storage::write_builder_properties(&env, &builder, &builder_properties);
+        storage::write_next_property_id(&env, next_id + 1);
+        storage::extend_instance(&env);
+
+        events::PropertyRegistered {
+            property_id: next_id,
+            builder,
+            name: property_name,
+            total_supply,
+        }
+        .publish(&env);
 
         next_id
     }
 
-    pub fn get_property(env: Env, property_id: u128) -> Property {
-        let properties: Map<u128, Property> = Self::get_properties_storage(&env);
-        let property = properties
-            .get(property_id)
-            .unwrap_or_else(|| panic_with_error!(&env, Error::InvalidProperty));
-        property
+    /// Number of fractions of `id` held by `owner`.
+    pub fn balance_of(env: Env, owner: Address, id: u128) -> i128 {
+        storage::read_balance(&env, &owner, id)
     }
 
+    /// Balances for each `(owners[i], ids[i])` pair, in the same order.
+    pub fn balance_of_batch(env: Env, owners: Vec<Address>, ids: Vec<u128>) -> Vec<i128> {
+        if owners.len() != ids.len() {
+            panic_with_error!(&env, Error::LengthMismatch);
+        }
+
+        let mut result = Vec::new(&env);
+        for (owner, id) in owners.iter().zip(ids.iter()) {
+            result.push_back(storage::read_balance(&env, &owner, id));
+        }
+        result
+    }
+
+    /// Total number of fractions issued for property `id`.
+    pub fn total_supply(env: Env, id: u128) -> i128 {
+        Self::get_property(env, id).total_supply
+    }
+
+    /// Admin-only. Caps the supply later mints of property `id` may reach.
+    /// `None` removes the cap.
+    pub fn set_max_supply(env: Env, admin: Address, id: u128, max_supply: Option<i128>) {
+        rbac::require_role(&env, &admin, Role::Admin);
+
+        let mut property = Self::get_property(env.clone(), id);
+        if let Some(cap) = max_supply {
+            if cap < property.total_supply {
+                panic_with_error!(&env, Error::SupplyCapExceeded);
+            }
+        }
+        property.max_supply = max_supply;
+        storage::write_property(&env, &property);
+
+        events::MaxSupplySet {
+            property_id: id,
+            max_supply,
+        }
+        .publish(&env);
+    }
+
+    /// Minter-only. Issues `amount` new fractions of `property_id` to `to`,
+    /// diluting existing holders. `price` and the other per-fraction figures
+    /// follow the new supply, since they are always derived from it.
+    pub fn mint(env: Env, minter: Address, property_id: u128, to: Address, amount: i128) {
+        rbac::require_role(&env, &minter, Role::Minter);
+        pause::require_property_not_paused(&env, property_id);
+
+        Self::_mint(&env, &to, property_id, amount);
+    }
 
     pub fn transfer_property(
         env: Env,
@@ -129,71 +436,364 @@ impl RealEstateTokenContract {
     ) {
         from.require_auth();
 
-        if amount <= 0 {
-            panic_with_error!(&env, Error::InsufficientBalance);
+        Self::_transfer(&env, &from, &to, property_id, amount);
+        receiver::notify(&env, &from, &from, &to, property_id, amount);
+    }
+
+    /// Moves `amounts[i]` fractions of `ids[i]` from `from` to `to`. Either every
+    /// transfer in the batch is applied or none is.
+    pub fn batch_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        ids: Vec<u128>,
+        amounts: Vec<i128>,
+    ) {
+        from.require_auth();
+
+        if ids.len() != amounts.len() {
+            panic_with_error!(&env, Error::LengthMismatch);
         }
 
-        // Verificar se a propriedade existe
-        let properties = Self::get_properties_storage(&env);
-        if !properties.contains_key(property_id) {
-            panic_with_error!(&env, Error::InvalidProperty);
+        for (property_id, amount) in ids.iter().zip(amounts.iter()) {
+            Self::_transfer(&env, &from, &to, property_id, amount);
+            receiver::notify(&env, &from, &from, &to, property_id, amount);
         }
+    }
 
-        let mut balances: Map<(Address, u128), i128> = env
-            .storage()
-            .instance()
-            .get(&"balances")
-            .unwrap_or(Map::new(&env));
+    /// Lets `operator` move any of `owner`'s fractions, across every property,
+    /// until the approval is revoked.
+    pub fn set_approval_for_all(env: Env, owner: Address, operator: Address, approved: bool) {
+        owner.require_auth();
+        pause::require_not_paused(&env);
 
-        // Obter saldo atual do remetente
-        let from_balance = balances.get((from.clone(), property_id)).unwrap_or(0);
-        if from_balance < amount {
-            panic_with_error!(&env, Error::InsufficientBalance);
+        storage::write_approval(&env, &owner, &operator, approved);
+
+        events::ApprovalForAll {
+            owner,
+            operator,
+            approved,
         }
+        .publish(&env);
+    }
 
-        // Atualizar saldo do remetente
-        let new_from_balance = from_balance - amount;
-        if new_from_balance > 0 {
-            balances.set((from.clone(), property_id), new_from_balance);
-        } else {
-            balances.remove((from.clone(), property_id));
+    pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        storage::read_approval(&env, &owner, &operator)
+    }
+
+    /// Lets `spender` move up to `amount` fractions of property `id` on behalf of
+    /// `owner` until `expiration_ledger`. Replaces any previous allowance.
+    pub fn approve(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        id: u128,
+        amount: i128,
+        expiration_ledger: u32,
+    ) {
+        owner.require_auth();
+        pause::require_property_not_paused(&env, id);
+
+        if amount < 0 {
+            panic_with_error!(&env, Error::InsufficientAllowance);
+        }
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            panic_with_error!(&env, Error::InvalidExpiration);
+        }
+
+        storage::write_allowance(
+            &env,
+            &owner,
+            &spender,
+            id,
+            &Allowance {
+                amount,
+                expiration_ledger,
+            },
+        );
+
+        events::PropertyApproved {
+            property_id: id,
+            owner,
+            spender,
+            amount,
+            expiration_ledger,
         }
+        .publish(&env);
+    }
 
-        // Atualizar saldo do destinatário
-        let to_balance = balances.get((to.clone(), property_id)).unwrap_or(0);
-        balances.set((to.clone(), property_id), to_balance + amount);
+    /// Remaining allowance of `spender` over `owner`'s fractions of `id`; zero once expired.
+    pub fn allowance(env: Env, owner: Address, spender: Address, id: u128) -> i128 {
+        storage::read_allowance(&env, &owner, &spender, id)
+            .map(|allowance| allowance.amount)
+            .unwrap_or(0)
+    }
 
-        // Salvar balanços atualizados
-        env.storage().instance().set(&"balances", &balances);
+    /// Moves fractions out of `from` on its behalf. `spender` must either be an
+    /// operator for `from` or hold a sufficient allowance for `id`, which is consumed.
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        id: u128,
+        amount: i128,
+    ) {
+        spender.require_auth();
 
-        // Emitir evento de transferência
-        env.events().publish(
-            ("property_transferred", property_id),
-            (from, to, amount),
+        if spender != from && !storage::read_approval(&env, &from, &spender) {
+            Self::_spend_allowance(&env, &from, &spender, id, amount);
+        }
+
+        Self::_transfer(&env, &from, &to, id, amount);
+        receiver::notify(&env, &spender, &from, &to, id, amount);
+    }
+
+    /// Whole fractions `investment` can buy, rounded down.
+    pub fn balance(env: Env, investment: i128, property: Property) -> i128 {
+        math::quote_purchase(&env, &property, investment).fractions
+    }
+
+    /// Decimals of every monetary amount the contract stores or quotes.
+    pub fn decimals() -> u32 {
+        math::DECIMALS
+    }
+
+    pub fn quote_price(env: Env, id: u128) -> i128 {
+        math::price(&env, &Self::get_property(env.clone(), id))
+    }
+
+    pub fn quote_funding_bps(env: Env, id: u128) -> u32 {
+        math::funding_bps(&env, &Self::get_property(env.clone(), id)) as u32
+    }
+
+    /// How many fractions of `id` a `budget` buys, what they cost and the change left over.
+    pub fn quote_purchase(env: Env, id: u128, budget: i128) -> PurchaseQuote {
+        math::quote_purchase(&env, &Self::get_property(env.clone(), id), budget)
+    }
+
+    fn _spend_allowance(env: &Env, owner: &Address, spender: &Address, id: u128, amount: i128) {
+        let allowance = storage::read_allowance(env, owner, spender, id)
+            .unwrap_or_else(|| panic_with_error!(env, Error::InsufficientAllowance));
+        if allowance.amount < amount {
+            panic_with_error!(env, Error::InsufficientAllowance);
+        }
+
+        storage::write_allowance(
+            env,
+            owner,
+            spender,
+            id,
+            &Allowance {
+                amount: allowance.amount - amount,
+                expiration_ledger: allowance.expiration_ledger,
+            },
         );
     }
 
-    pub fn balance(investment: i128, property: Property) -> i128{
-        let price = Self::price(property: Property)
-        investment/price
+    /// Every check a transfer must pass, without side effects. Shared by
+    /// `_transfer` and `can_transfer` so both always agree.
+    fn _check_transfer(
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        property_id: u128,
+        amount: i128,
+    ) -> Result<(), Error> {
+        if amount <= 0 {
+            return Err(Error::InsufficientBalance);
+        }
+
+        // Verificar se a propriedade existe
+        let property = storage::read_property(env, property_id).ok_or(Error::InvalidProperty)?;
+        pause::check_property_not_paused(env, property_id)?;
+        lifecycle::check_transferable(&property)?;
+
+        // Obter saldo atual do remetente
+        let from_balance = storage::read_balance(env, from, property_id);
+        if from_balance < amount {
+            return Err(Error::InsufficientBalance);
+        }
+        vesting::check_unlocked(env, from, property_id, amount, from_balance)?;
+        freeze::check_transfer(env, from, to, property_id, amount, from_balance)?;
+
+        let to_balance = storage::read_balance(env, to, property_id);
+        compliance::check_transfer_rules(
+            env,
+            from,
+            to,
+            property_id,
+            amount,
+            from_balance,
+            to_balance,
+        )?;
+        if from != to {
+            limits::check_holding(
+                env,
+                &property,
+                to,
+                to_balance,
+                amount,
+                property.total_supply,
+            )?;
+        }
+        Ok(())
     }
 
-    pub fn price(property: Property) -> i128{
-        property.ele_quer/property.total_supply
+    fn _transfer(env: &Env, from: &Address, to: &Address, property_id: u128, amount: i128) {
+        if let Err(e) = Self::_check_transfer(env, from, to, property_id, amount) {
+            panic_with_error!(env, e);
+        }
+        Self::_move(env, from, to, property_id, amount);
     }
 
-    pub fn percentual(property: Property) -> u128{
-        property.ele_tem/property.ele_quer
+    /// Moves `amount` fractions of `property_id` from `from` to `to` and keeps
+    /// every per-holder index in step. Performs no checks of its own.
+    fn _move(env: &Env, from: &Address, to: &Address, property_id: u128, amount: i128) {
+        distribution::settle(env, from, property_id);
+        distribution::settle(env, to, property_id);
+        snapshots::update_balance(env, from, property_id);
+        snapshots::update_balance(env, to, property_id);
+
+        // Atualizar saldos do remetente e do destinatário
+        let from_balance = storage::read_balance(env, from, property_id);
+        storage::write_balance(env, from, property_id, from_balance - amount);
+        let to_balance = storage::read_balance(env, to, property_id);
+        storage::write_balance(env, to, property_id, to_balance + amount);
+        holders::update(env, from, property_id, from_balance, from_balance - amount);
+        holders::update(env, to, property_id, to_balance, to_balance + amount);
+        if from != to {
+            let property = storage::read_property(env, property_id)
+                .unwrap_or_else(|| panic_with_error!(env, Error::InvalidProperty));
+            limits::record_acquisition(env, &property, to, amount);
+        }
+        storage::extend_instance(env);
+
+        // Emitir evento de transferência
+        events::PropertyTransferred {
+            property_id,
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+        }
+        .publish(env);
     }
 
-    fn _verify_builder(env: &Env, builder: &Address) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&"admin")
+    /// Issues `amount` new fractions of `property_id` to `to`, growing its
+    /// total supply up to `max_supply`.
+    fn _mint(env: &Env, to: &Address, property_id: u128, amount: i128) {
+        if amount <= 0 {
+            panic_with_error!(env, Error::InsufficientBalance);
+        }
+        let mut property = storage::read_property(env, property_id)
             .unwrap_or_else(|| panic_with_error!(env, Error::InvalidProperty));
-        if *builder != admin {
-            panic_with_error!(env, Error::Unauthorized);
+        if matches!(
+            property.status,
+            PropertyStatus::Sold | PropertyStatus::Cancelled
+        ) {
+            panic_with_error!(env, Error::InvalidPropertyStatus);
+        }
+        let total_supply = property
+            .total_supply
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(env, Error::ArithmeticError));
+        if matches!(property.max_supply, Some(cap) if total_supply > cap) {
+            panic_with_error!(env, Error::SupplyCapExceeded);
+        }
+        let balance = storage::read_balance(env, to, property_id);
+        if let Err(e) = freeze::check_recipient(env, to) {
+            panic_with_error!(env, e);
+        }
+        if let Err(e) = compliance::check_mint_rules(env, to, property_id, balance) {
+            panic_with_error!(env, e);
+        }
+        if let Err(e) = limits::check_holding(env, &property, to, balance, amount, total_supply) {
+            panic_with_error!(env, e);
+        }
+
+        distribution::settle(env, to, property_id);
+        snapshots::update_balance(env, to, property_id);
+        snapshots::update_supply(env, property_id);
+
+        storage::write_balance(env, to, property_id, balance + amount);
+        limits::record_acquisition(env, &property, to, amount);
+        property.total_supply = total_supply;
+        storage::write_property(env, &property);
+        holders::update(env, to, property_id, balance, balance + amount);
+        storage::extend_instance(env);
+
+        events::FractionsMinted {
+            property_id,
+            to: to.clone(),
+            amount,
+            total_supply,
+        }
+        .publish(env);
+    }
+
+    /// Destroys `amount` of `holder`'s fractions of `property_id`, shrinking
+    /// its total supply.
+    fn _burn(env: &Env, holder: &Address, property_id: u128, amount: i128) {
+        if amount <= 0 {
+            panic_with_error!(env, Error::InsufficientBalance);
         }
+        let mut property = storage::read_property(env, property_id)
+            .unwrap_or_else(|| panic_with_error!(env, Error::InvalidProperty));
+        let balance = storage::read_balance(env, holder, property_id);
+        if balance < amount {
+            panic_with_error!(env, Error::InsufficientBalance);
+        }
+        if let Err(e) = vesting::check_unlocked(env, holder, property_id, amount, balance)
+            .and_then(|_| freeze::check_holder(env, holder, property_id, amount, balance))
+        {
+            panic_with_error!(env, e);
+        }
+
+        distribution::settle(env, holder, property_id);
+        snapshots::update_balance(env, holder, property_id);
+        snapshots::update_supply(env, property_id);
+
+        storage::write_balance(env, holder, property_id, balance - amount);
+        property.total_supply -= amount;
+        storage::write_property(env, &property);
+        holders::update(env, holder, property_id, balance, balance - amount);
+        storage::extend_instance(env);
+
+        events::FractionsBurned {
+            property_id,
+            holder: holder.clone(),
+            amount,
+            total_supply: property.total_supply,
+        }
+        .publish(env);
+    }
+
+    fn _verify_builder(env: &Env, builder: &Address, nome_construtora: &String) {
+        let profile = storage::read_builder(env, builder)
+            .unwrap_or_else(|| panic_with_error!(env, Error::Unauthorized));
+        if profile.status != BuilderStatus::Active {
+            panic_with_error!(env, Error::BuilderNotActive);
+        }
+        if profile.legal_name != *nome_construtora {
+            panic_with_error!(env, Error::BuilderNameMismatch);
+        }
+    }
+}
+
+/// The entrypoints other contracts call, declared in `ecr1155-interface` so
+/// their signatures cannot drift from the client those contracts use.
+#[contractimpl]
+impl RealEstateToken for RealEstateTokenContract {
+    fn get_property(env: Env, property_id: u128) -> Property {
+        storage::read_property(&env, property_id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::InvalidProperty))
+    }
+
+    fn price(env: Env, property: Property) -> i128 {
+        math::price(&env, &property)
+    }
+
+    fn percentual(env: Env, property: Property) -> u128 {
+        math::funding_bps(&env, &property) as u128
     }
 }
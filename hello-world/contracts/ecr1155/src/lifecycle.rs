@@ -0,0 +1,60 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env};
+
+use crate::{
+    events, pause, storage, Error, Property, PropertyStatus, RealEstateTokenContract,
+    RealEstateTokenContractArgs, RealEstateTokenContractClient, Role,
+};
+
+/// Rejects the call unless `property` is currently in `status`.
+pub(crate) fn require_status(env: &Env, property: &Property, status: PropertyStatus) {
+    if property.status != status {
+        panic_with_error!(env, Error::InvalidPropertyStatus);
+    }
+}
+
+/// Fractions of a cancelled property are frozen where they are.
+pub(crate) fn check_transferable(property: &Property) -> Result<(), Error> {
+    if property.status == PropertyStatus::Cancelled {
+        return Err(Error::InvalidPropertyStatus);
+    }
+    Ok(())
+}
+
+/// Moves `property` to `status` and persists it, rejecting transitions not
+/// allowed by [`PropertyStatus::can_transition_to`].
+pub(crate) fn transition(env: &Env, property: &mut Property, status: PropertyStatus) {
+    if !property.status.can_transition_to(status) {
+        panic_with_error!(env, Error::InvalidStatusTransition);
+    }
+
+    let previous = property.status;
+    property.status = status;
+    property.status_updated_at = env.ledger().timestamp();
+    storage::write_property(env, property);
+
+    events::PropertyStatusChanged {
+        property_id: property.id,
+        previous,
+        status,
+        changed_at: property.status_updated_at,
+    }
+    .publish(env);
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Moves property `id` to `status`. Callable by the property's builder or
+    /// an `Operator`; only the transitions allowed by
+    /// [`PropertyStatus::can_transition_to`] are accepted.
+    pub fn set_property_status(env: Env, caller: Address, id: u128, status: PropertyStatus) {
+        caller.require_auth();
+        pause::require_property_not_paused(&env, id);
+
+        let mut property = storage::read_property(&env, id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::InvalidProperty));
+        if caller != property.builder && !rbac::has_role(&env, Role::Operator, &caller) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        transition(&env, &mut property, status);
+    }
+}
@@ -0,0 +1,199 @@
+//! Holding caps and concentration limits on what one investor may own.
+//!
+//! Each property can cap the balance of any single holder, both in fractions
+//! and as a share of its supply. On top of that, each investor category can
+//! have a ceiling on the value acquired over the last 365 days, across all
+//! properties, as CVM Resolution 88 requires for retail investors. Acquisitions
+//! are valued at the property's price per fraction and only logged while their
+//! category has a ceiling. Accounts without an identity count as `Retail`. The
+//! property's builder is exempt from every limit on its own property.
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Vec};
+
+use crate::{
+    events,
+    math::{self, Rounding, BPS},
+    storage, Error, InvestorCategory, Property, RealEstateToken, RealEstateTokenContract,
+    RealEstateTokenContractArgs, RealEstateTokenContractClient, Role,
+};
+
+pub const YEAR_IN_SECONDS: u64 = 365 * 86_400;
+
+/// Per-property caps on a single holder's balance. Zero disables a cap.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HoldingLimits {
+    /// Most fractions one holder may own.
+    pub max_balance: i128,
+    /// Largest share of `total_supply` one holder may own, in basis points.
+    pub max_share_bps: u32,
+}
+
+/// Value acquired by an investor at a point in time.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Acquisition {
+    pub at: u64,
+    pub value: i128,
+}
+
+/// Value of `amount` fractions at the property's current price. Nothing is
+/// charged for fractions of a property with no supply yet.
+fn value_of(env: &Env, property: &Property, amount: i128) -> i128 {
+    if property.total_supply == 0 {
+        return 0;
+    }
+    math::cost_of(env, property, amount)
+}
+
+fn category_of(env: &Env, investor: &Address) -> InvestorCategory {
+    storage::read_identity(env, investor)
+        .map(|identity| identity.category)
+        .unwrap_or(InvestorCategory::Retail)
+}
+
+/// `investor`'s acquisitions still inside the rolling year.
+fn recent_acquisitions(env: &Env, investor: &Address) -> Vec<Acquisition> {
+    let since = env.ledger().timestamp().saturating_sub(YEAR_IN_SECONDS);
+    let mut recent = Vec::new(env);
+    for acquisition in storage::read_acquisitions(env, investor).iter() {
+        if acquisition.at > since {
+            recent.push_back(acquisition);
+        }
+    }
+    recent
+}
+
+fn acquired_this_year(env: &Env, investor: &Address) -> i128 {
+    recent_acquisitions(env, investor)
+        .iter()
+        .map(|acquisition| acquisition.value)
+        .sum()
+}
+
+/// Rejects `holder` ending up with `balance + amount` fractions of `property`
+/// once its supply is `total_supply`.
+pub(crate) fn check_holding(
+    env: &Env,
+    property: &Property,
+    holder: &Address,
+    balance: i128,
+    amount: i128,
+    total_supply: i128,
+) -> Result<(), Error> {
+    if *holder == property.builder {
+        return Ok(());
+    }
+
+    let limits = storage::read_holding_limits(env, property.id);
+    let new_balance = balance + amount;
+    if limits.max_balance > 0 && new_balance > limits.max_balance {
+        return Err(Error::HoldingLimitExceeded);
+    }
+    if limits.max_share_bps > 0 && new_balance * BPS > limits.max_share_bps as i128 * total_supply {
+        return Err(Error::ConcentrationLimitExceeded);
+    }
+
+    let cap = storage::read_annual_cap(env, category_of(env, holder));
+    if cap > 0 {
+        let value = value_of(env, property, amount);
+        if acquired_this_year(env, holder) + value > cap {
+            return Err(Error::AnnualCapExceeded);
+        }
+    }
+    Ok(())
+}
+
+/// Logs `holder` acquiring `amount` fractions of `property` against its
+/// category's annual ceiling, if it has one.
+pub(crate) fn record_acquisition(env: &Env, property: &Property, holder: &Address, amount: i128) {
+    if *holder == property.builder || storage::read_annual_cap(env, category_of(env, holder)) == 0 {
+        return;
+    }
+    let mut acquisitions = recent_acquisitions(env, holder);
+    acquisitions.push_back(Acquisition {
+        at: env.ledger().timestamp(),
+        value: value_of(env, property, amount),
+    });
+    storage::write_acquisitions(env, holder, &acquisitions);
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Compliance-only. Replaces the holding caps of property `id`.
+    pub fn set_holding_limits(env: Env, officer: Address, id: u128, limits: HoldingLimits) {
+        rbac::require_role(&env, &officer, Role::Compliance);
+
+        if storage::read_property(&env, id).is_none() {
+            panic_with_error!(&env, Error::InvalidProperty);
+        }
+        if limits.max_balance < 0 || limits.max_share_bps as i128 > BPS {
+            panic_with_error!(&env, Error::InvalidLimits);
+        }
+        storage::write_holding_limits(&env, id, &limits);
+
+        events::HoldingLimitsSet {
+            property_id: id,
+            limits,
+        }
+        .publish(&env);
+    }
+
+    pub fn holding_limits(env: Env, id: u128) -> HoldingLimits {
+        storage::read_holding_limits(&env, id)
+    }
+
+    /// Compliance-only. Sets the value investors of `category` may acquire
+    /// over any 365 days, in payment-token base units. Zero removes the ceiling.
+    pub fn set_annual_cap(env: Env, officer: Address, category: InvestorCategory, cap: i128) {
+        rbac::require_role(&env, &officer, Role::Compliance);
+
+        if cap < 0 {
+            panic_with_error!(&env, Error::InvalidLimits);
+        }
+        storage::write_annual_cap(&env, category, cap);
+
+        events::AnnualCapSet { category, cap }.publish(&env);
+    }
+
+    pub fn annual_cap(env: Env, category: InvestorCategory) -> i128 {
+        storage::read_annual_cap(&env, category)
+    }
+
+    /// Fractions of `id` that `holder` may still receive under the property's
+    /// holding caps at its current supply. `None` if uncapped.
+    pub fn holding_headroom(env: Env, holder: Address, id: u128) -> Option<i128> {
+        let property = Self::get_property(env.clone(), id);
+        if holder == property.builder {
+            return None;
+        }
+
+        let limits = storage::read_holding_limits(&env, id);
+        let balance = storage::read_balance(&env, &holder, id);
+        let mut headroom: Option<i128> = None;
+        if limits.max_balance > 0 {
+            headroom = Some(limits.max_balance - balance);
+        }
+        if limits.max_share_bps > 0 {
+            let max_share = math::mul_div(
+                &env,
+                property.total_supply,
+                limits.max_share_bps as i128,
+                BPS,
+                Rounding::Down,
+            );
+            let share_headroom = max_share - balance;
+            headroom = Some(headroom.map_or(share_headroom, |h| h.min(share_headroom)));
+        }
+        headroom.map(|h| h.max(0))
+    }
+
+    /// Value `investor` may still acquire under its category's annual ceiling
+    /// right now. `None` if its category has no ceiling.
+    pub fn annual_headroom(env: Env, investor: Address) -> Option<i128> {
+        let cap = storage::read_annual_cap(&env, category_of(&env, &investor));
+        if cap == 0 {
+            return None;
+        }
+        Some((cap - acquired_this_year(&env, &investor)).max(0))
+    }
+}
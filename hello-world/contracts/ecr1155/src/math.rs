@@ -0,0 +1,99 @@
+//! Fixed-point helpers for property pricing.
+//!
+//! Monetary amounts (`ele_quer`, `ele_tem`, payments) are token base units with
+//! [`DECIMALS`] decimals, like every Stellar asset, so `10_000_000` is one BRL
+//! or one USDC. Every division states its rounding direction; amounts owed to
+//! the protocol round up and amounts handed out round down, so the contract
+//! never issues more than it is paid for.
+use soroban_sdk::{contracttype, panic_with_error, Env};
+
+use crate::{Error, Property};
+
+pub const DECIMALS: u32 = 7;
+pub const BPS: i128 = 10_000;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// Result of spending a budget on fractions of one property.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PurchaseQuote {
+    pub fractions: i128,
+    pub cost: i128,
+    pub change: i128,
+}
+
+/// `a * b / denominator` for non-negative operands, rounded as requested.
+/// Panics with `ArithmeticError` on overflow or a non-positive denominator.
+pub fn mul_div(env: &Env, a: i128, b: i128, denominator: i128, rounding: Rounding) -> i128 {
+    if denominator <= 0 || a < 0 || b < 0 {
+        panic_with_error!(env, Error::ArithmeticError);
+    }
+    let product = a
+        .checked_mul(b)
+        .unwrap_or_else(|| panic_with_error!(env, Error::ArithmeticError));
+    let quotient = product / denominator;
+    match rounding {
+        Rounding::Up if product % denominator != 0 => quotient + 1,
+        _ => quotient,
+    }
+}
+
+/// Price of one fraction, rounded up.
+pub fn price(env: &Env, property: &Property) -> i128 {
+    mul_div(
+        env,
+        property.ele_quer,
+        1,
+        property.total_supply,
+        Rounding::Up,
+    )
+}
+
+/// Price of `amount` fractions, rounded up. Computed from the totals rather
+/// than from [`price`] so the rounding is applied once, not per fraction.
+pub fn cost_of(env: &Env, property: &Property, amount: i128) -> i128 {
+    mul_div(
+        env,
+        amount,
+        property.ele_quer,
+        property.total_supply,
+        Rounding::Up,
+    )
+}
+
+/// Share of the target raised so far, in basis points, rounded down. A
+/// property without a target (some migrated legacy ones) counts as 0.
+pub fn funding_bps(env: &Env, property: &Property) -> i128 {
+    if property.ele_quer <= 0 {
+        return 0;
+    }
+    mul_div(
+        env,
+        property.ele_tem,
+        BPS,
+        property.ele_quer,
+        Rounding::Down,
+    )
+}
+
+/// Most fractions `budget` can pay for, and what is left of it afterwards.
+pub fn quote_purchase(env: &Env, property: &Property, budget: i128) -> PurchaseQuote {
+    let fractions = mul_div(
+        env,
+        budget,
+        property.total_supply,
+        property.ele_quer,
+        Rounding::Down,
+    );
+    let cost = cost_of(env, property, fractions);
+    PurchaseQuote {
+        fractions,
+        cost,
+        change: budget - cost,
+    }
+}
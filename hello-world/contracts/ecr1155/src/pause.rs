@@ -0,0 +1,88 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env};
+
+use crate::{
+    events, storage, Error, RealEstateTokenContract, RealEstateTokenContractArgs,
+    RealEstateTokenContractClient, Role,
+};
+
+pub(crate) fn check_not_paused(env: &Env) -> Result<(), Error> {
+    if storage::has_legacy_storage(env) {
+        return Err(Error::MigrationPending);
+    }
+    if storage::read_paused(env) {
+        return Err(Error::ContractPaused);
+    }
+    Ok(())
+}
+
+pub(crate) fn check_property_not_paused(env: &Env, id: u128) -> Result<(), Error> {
+    check_not_paused(env)?;
+    if storage::read_property_paused(env, id) {
+        return Err(Error::PropertyPaused);
+    }
+    Ok(())
+}
+
+/// Rejects the call while the whole contract is paused.
+pub(crate) fn require_not_paused(env: &Env) {
+    if let Err(e) = check_not_paused(env) {
+        panic_with_error!(env, e);
+    }
+}
+
+/// Rejects the call while the contract or property `id` is paused.
+pub(crate) fn require_property_not_paused(env: &Env, id: u128) {
+    if let Err(e) = check_property_not_paused(env, id) {
+        panic_with_error!(env, e);
+    }
+}
+
+/// Pausing halts registration, transfers and approvals. Role management and
+/// the builder registry stay available so the platform can fix whatever caused
+/// the pause.
+#[contractimpl]
+impl RealEstateTokenContract {
+    pub fn pause(env: Env, pauser: Address) {
+        rbac::require_role(&env, &pauser, Role::Pauser);
+        storage::write_paused(&env, true);
+        events::Paused { pauser }.publish(&env);
+    }
+
+    pub fn unpause(env: Env, pauser: Address) {
+        rbac::require_role(&env, &pauser, Role::Pauser);
+        storage::write_paused(&env, false);
+        events::Unpaused { pauser }.publish(&env);
+    }
+
+    /// Halts every transfer of property `id`, e.g. while it is under legal dispute.
+    pub fn pause_property(env: Env, pauser: Address, id: u128) {
+        rbac::require_role(&env, &pauser, Role::Pauser);
+        if storage::read_property(&env, id).is_none() {
+            panic_with_error!(&env, Error::InvalidProperty);
+        }
+        storage::write_property_paused(&env, id, true);
+        events::PropertyPaused {
+            property_id: id,
+            pauser,
+        }
+        .publish(&env);
+    }
+
+    pub fn unpause_property(env: Env, pauser: Address, id: u128) {
+        rbac::require_role(&env, &pauser, Role::Pauser);
+        storage::write_property_paused(&env, id, false);
+        events::PropertyUnpaused {
+            property_id: id,
+            pauser,
+        }
+        .publish(&env);
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        storage::read_paused(&env)
+    }
+
+    pub fn is_property_paused(env: Env, id: u128) -> bool {
+        storage::read_property_paused(&env, id)
+    }
+}
@@ -0,0 +1,98 @@
+//! Notifying contracts that receive fractions.
+//!
+//! A contract that wants to react to incoming fractions (an escrow, a
+//! collateral vault, a marketplace) implements [`FractionReceiver`] and opts in
+//! with `set_fraction_receiver`. Every transfer to it is then followed by a call
+//! to `on_fraction_received`, and the whole transfer reverts unless the hook
+//! returns `true`. `transfer_and_call` always calls the hook, opted in or not.
+//!
+//! The hook runs after balances are updated. Soroban forbids re-entering the
+//! token contract from inside it, so the receiver must rely on the arguments
+//! rather than query balances back.
+use soroban_sdk::{contractclient, contractimpl, panic_with_error, Address, Bytes, Env};
+
+use crate::{
+    events, storage, Error, RealEstateTokenContract, RealEstateTokenContractArgs,
+    RealEstateTokenContractClient,
+};
+
+/// Interface a receiving contract implements.
+#[contractclient(name = "FractionReceiverClient")]
+pub trait FractionReceiver {
+    /// Called once `amount` fractions of property `id` have moved from `from`
+    /// to the receiver at `operator`'s request. Returns whether the receiver
+    /// accepts them.
+    fn on_fraction_received(
+        env: Env,
+        operator: Address,
+        from: Address,
+        id: u128,
+        amount: i128,
+        data: Bytes,
+    ) -> bool;
+}
+
+/// Calls `to`'s hook and reverts the transfer unless it accepts.
+pub(crate) fn call(
+    env: &Env,
+    operator: &Address,
+    from: &Address,
+    to: &Address,
+    id: u128,
+    amount: i128,
+    data: &Bytes,
+) {
+    let accepted = FractionReceiverClient::new(env, to)
+        .on_fraction_received(operator, from, &id, &amount, data);
+    if !accepted {
+        panic_with_error!(env, Error::ReceiverRejected);
+    }
+}
+
+/// Calls `to`'s hook with empty data if it opted in.
+pub(crate) fn notify(
+    env: &Env,
+    operator: &Address,
+    from: &Address,
+    to: &Address,
+    id: u128,
+    amount: i128,
+) {
+    if storage::read_fraction_receiver(env, to) {
+        call(env, operator, from, to, id, amount, &Bytes::new(env));
+    }
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Opts `receiver` in or out of `on_fraction_received` calls on every
+    /// incoming transfer. Requires the receiver's own authorization, which a
+    /// contract gives implicitly when it makes this call itself.
+    pub fn set_fraction_receiver(env: Env, receiver: Address, enabled: bool) {
+        receiver.require_auth();
+        storage::write_fraction_receiver(&env, &receiver, enabled);
+        events::FractionReceiverSet { receiver, enabled }.publish(&env);
+    }
+
+    pub fn is_fraction_receiver(env: Env, receiver: Address) -> bool {
+        storage::read_fraction_receiver(&env, &receiver)
+    }
+
+    /// Transfers like `transfer_property`, then calls `to`'s
+    /// `on_fraction_received` with `data`, reverting unless it accepts. Lets a
+    /// contract take fractions and act on them (e.g. lock them as collateral)
+    /// in a single transaction.
+    pub fn transfer_and_call(
+        env: Env,
+        from: Address,
+        to: Address,
+        property_id: u128,
+        amount: i128,
+        data: Bytes,
+    ) {
+        from.require_auth();
+
+        Self::_transfer(&env, &from, &to, property_id, amount);
+        call(&env, &from, &from, &to, property_id, amount, &data);
+    }
+}
@@ -0,0 +1,279 @@
+//! Transfers imposed by a `Compliance` officer, without the holder's signature:
+//! court orders, and investors who lost their keys.
+//!
+//! Each one carries the hash of the document that justifies it (a court
+//! order, a recovery request) and is published with it. While the admin has
+//! set a recovery delay, requests are queued and can only be executed once the
+//! delay has passed, leaving the holder time to contest them. They bypass
+//! pauses, lifecycle restrictions, vesting lock-ups and holding limits, but the
+//! recipient must still meet each property's identity requirements.
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, BytesN, Env, Vec};
+
+use crate::{
+    compliance, events, storage, Error, RealEstateTokenContract, RealEstateTokenContractArgs,
+    RealEstateTokenContractClient, Role,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecoveryAction {
+    /// Moves `amount` fractions of one property: `(from, to, property_id, amount)`.
+    Transfer(Address, Address, u128, i128),
+    /// Moves everything an account holds to a new one: `(old, new)`.
+    Account(Address, Address),
+}
+
+/// A forced transfer or account recovery waiting for the recovery delay.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingRecovery {
+    pub id: u64,
+    pub operator: Address,
+    pub action: RecoveryAction,
+    pub reason_hash: BytesN<32>,
+    /// Ledger timestamp from which the request can be executed.
+    pub eta: u64,
+}
+
+fn validate(env: &Env, action: &RecoveryAction) {
+    match action {
+        RecoveryAction::Transfer(from, to, property_id, amount) => {
+            if from == to {
+                panic_with_error!(env, Error::InvalidRecovery);
+            }
+            if *amount <= 0 {
+                panic_with_error!(env, Error::InsufficientBalance);
+            }
+            if storage::read_property(env, *property_id).is_none() {
+                panic_with_error!(env, Error::InvalidProperty);
+            }
+        }
+        RecoveryAction::Account(old, new) => {
+            if old == new {
+                panic_with_error!(env, Error::InvalidRecovery);
+            }
+        }
+    }
+}
+
+fn require_recipient(env: &Env, to: &Address, property_id: u128) {
+    let rules = storage::read_transfer_rules(env, property_id);
+    if let Err(e) = compliance::check_recipient(env, &rules, to) {
+        panic_with_error!(env, e);
+    }
+}
+
+/// Runs `action` now, or queues it if a recovery delay is set. Returns the
+/// id of the queued request.
+fn submit(
+    env: &Env,
+    operator: Address,
+    action: RecoveryAction,
+    reason_hash: BytesN<32>,
+) -> Option<u64> {
+    rbac::require_role(env, &operator, Role::Compliance);
+    validate(env, &action);
+
+    let delay = storage::read_recovery_delay(env);
+    if delay == 0 {
+        execute(env, &operator, &action, &reason_hash);
+        return None;
+    }
+
+    let id = storage::read_next_recovery_id(env);
+    let pending = PendingRecovery {
+        id,
+        operator,
+        action,
+        reason_hash,
+        eta: env.ledger().timestamp() + delay,
+    };
+    storage::write_pending_recovery(env, &pending);
+    storage::write_next_recovery_id(env, id + 1);
+    storage::extend_instance(env);
+
+    events::RecoveryQueued {
+        request_id: id,
+        operator: pending.operator,
+        action: pending.action,
+        reason_hash: pending.reason_hash,
+        eta: pending.eta,
+    }
+    .publish(env);
+
+    Some(id)
+}
+
+fn execute(env: &Env, operator: &Address, action: &RecoveryAction, reason_hash: &BytesN<32>) {
+    // Unlike a pause, a pending legacy migration would overwrite what moves.
+    if storage::has_legacy_storage(env) {
+        panic_with_error!(env, Error::MigrationPending);
+    }
+    match action {
+        RecoveryAction::Transfer(from, to, property_id, amount) => {
+            let property_id = *property_id;
+            if storage::read_balance(env, from, property_id) < *amount {
+                panic_with_error!(env, Error::InsufficientBalance);
+            }
+            require_recipient(env, to, property_id);
+            RealEstateTokenContract::_move(env, from, to, property_id, *amount);
+
+            events::ForcedTransfer {
+                property_id,
+                operator: operator.clone(),
+                from: from.clone(),
+                to: to.clone(),
+                amount: *amount,
+                reason_hash: reason_hash.clone(),
+            }
+            .publish(env);
+        }
+        RecoveryAction::Account(old, new) => {
+            // The portfolio index shrinks as balances move, so read it first.
+            let count = storage::read_portfolio_count(env, old);
+            let mut ids = Vec::new(env);
+            for index in 0..count {
+                if let Some(id) = storage::read_portfolio_at(env, old, index) {
+                    ids.push_back(id);
+                }
+            }
+
+            for property_id in ids.iter() {
+                require_recipient(env, new, property_id);
+                // `new` would end up with one of the schedules and the
+                // fractions of the other unlocked.
+                if storage::read_vesting(env, old, property_id).is_some()
+                    && storage::read_vesting(env, new, property_id).is_some()
+                {
+                    panic_with_error!(env, Error::InvalidRecovery);
+                }
+                let balance = storage::read_balance(env, old, property_id);
+                RealEstateTokenContract::_move(env, old, new, property_id, balance);
+                move_holder_state(env, old, new, property_id);
+            }
+
+            events::AccountRecovered {
+                operator: operator.clone(),
+                old: old.clone(),
+                new: new.clone(),
+                properties: ids,
+                reason_hash: reason_hash.clone(),
+            }
+            .publish(env);
+        }
+    }
+}
+
+/// Hands `old`'s unclaimed income and vesting schedule on `property_id` over
+/// to `new`. Balances must already have been settled by the move.
+fn move_holder_state(env: &Env, old: &Address, new: &Address, property_id: u128) {
+    let mut old_rewards = storage::read_holder_rewards(env, old, property_id);
+    if old_rewards.pending > 0 {
+        let mut new_rewards = storage::read_holder_rewards(env, new, property_id);
+        new_rewards.pending += old_rewards.pending;
+        old_rewards.pending = 0;
+        storage::write_holder_rewards(env, new, property_id, &new_rewards);
+        storage::write_holder_rewards(env, old, property_id, &old_rewards);
+    }
+
+    if let Some(schedule) = storage::read_vesting(env, old, property_id) {
+        storage::write_vesting(env, new, property_id, &schedule);
+        storage::remove_vesting(env, old, property_id);
+    }
+}
+
+pub(crate) fn apply_recovery_delay(env: &Env, delay: u64) {
+    storage::write_recovery_delay(env, delay);
+    storage::extend_instance(env);
+    events::RecoveryDelaySet { delay }.publish(env);
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Admin-only. Timelocked. Seconds a forced transfer or account recovery
+    /// must wait between being requested and executed. Zero executes them
+    /// immediately.
+    pub fn set_recovery_delay(env: Env, admin: Address, delay: u64) {
+        rbac::require_role(&env, &admin, Role::Admin);
+        rbac::timelock::require_unlocked(&env);
+        apply_recovery_delay(&env, delay);
+    }
+
+    pub fn recovery_delay(env: Env) -> u64 {
+        storage::read_recovery_delay(&env)
+    }
+
+    /// Compliance-only. Moves `amount` fractions of `id` from `from` to `to`
+    /// without `from`'s authorization. Returns the request id when queued
+    /// behind the recovery delay, `None` when executed right away.
+    pub fn forced_transfer(
+        env: Env,
+        operator: Address,
+        from: Address,
+        to: Address,
+        id: u128,
+        amount: i128,
+        reason_hash: BytesN<32>,
+    ) -> Option<u64> {
+        submit(
+            &env,
+            operator,
+            RecoveryAction::Transfer(from, to, id, amount),
+            reason_hash,
+        )
+    }
+
+    /// Compliance-only. Moves every fraction `old` holds, along with its
+    /// unclaimed income and vesting schedules, to `new`. Fails with
+    /// `InvalidRecovery` if both have a schedule on the same property. Returns
+    /// the request id when queued behind the recovery delay, `None` when
+    /// executed right away.
+    pub fn recover_account(
+        env: Env,
+        operator: Address,
+        old: Address,
+        new: Address,
+        reason_hash: BytesN<32>,
+    ) -> Option<u64> {
+        submit(
+            &env,
+            operator,
+            RecoveryAction::Account(old, new),
+            reason_hash,
+        )
+    }
+
+    /// Compliance-only. Executes a queued request once its delay has passed.
+    pub fn execute_recovery(env: Env, operator: Address, request_id: u64) {
+        rbac::require_role(&env, &operator, Role::Compliance);
+
+        let pending = storage::read_pending_recovery(&env, request_id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::RecoveryNotFound));
+        if env.ledger().timestamp() < pending.eta {
+            panic_with_error!(&env, Error::RecoveryNotReady);
+        }
+        storage::remove_pending_recovery(&env, request_id);
+
+        execute(&env, &operator, &pending.action, &pending.reason_hash);
+    }
+
+    /// Compliance-only. Drops a queued request.
+    pub fn cancel_recovery(env: Env, operator: Address, request_id: u64) {
+        rbac::require_role(&env, &operator, Role::Compliance);
+
+        if storage::read_pending_recovery(&env, request_id).is_none() {
+            panic_with_error!(&env, Error::RecoveryNotFound);
+        }
+        storage::remove_pending_recovery(&env, request_id);
+
+        events::RecoveryCancelled {
+            request_id,
+            operator,
+        }
+        .publish(&env);
+    }
+
+    pub fn get_recovery(env: Env, request_id: u64) -> Option<PendingRecovery> {
+        storage::read_pending_recovery(&env, request_id)
+    }
+}
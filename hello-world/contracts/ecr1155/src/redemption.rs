@@ -0,0 +1,132 @@
+//! Winding up a sold property.
+//!
+//! Sale proceeds are deposited once the building is sold, and each holder then
+//! burns their fractions for the matching share of what is left. A redemption
+//! pays `amount * remaining / total_supply` rounded down, so whatever rounding
+//! leaves behind goes to the last holder to redeem and the pool is empty
+//! exactly when the supply reaches zero.
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, token, Address, Env};
+
+use crate::{
+    events, lifecycle,
+    math::{self, Rounding},
+    pause, storage, Error, PropertyStatus, RealEstateTokenContract, RealEstateTokenContractArgs,
+    RealEstateTokenContractClient, Role,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SaleProceeds {
+    pub token: Address,
+    /// Proceeds not yet paid out to redeeming holders.
+    pub remaining: i128,
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Deposits `amount` of `token` as the proceeds of selling property `id`,
+    /// moving it from `Delivered` to `Sold` if it is not there yet. Callable by
+    /// the property's builder or an `Admin`. Further deposits top up the pool
+    /// and must use the same token.
+    pub fn deposit_sale_proceeds(
+        env: Env,
+        depositor: Address,
+        id: u128,
+        token: Address,
+        amount: i128,
+    ) {
+        depositor.require_auth();
+        pause::require_property_not_paused(&env, id);
+
+        let mut property = storage::read_property(&env, id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::InvalidProperty));
+        if depositor != property.builder && !rbac::has_role(&env, Role::Admin, &depositor) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InsufficientBalance);
+        }
+        match property.status {
+            PropertyStatus::Sold => {}
+            PropertyStatus::Delivered => {
+                lifecycle::transition(&env, &mut property, PropertyStatus::Sold)
+            }
+            _ => panic_with_error!(&env, Error::InvalidPropertyStatus),
+        }
+
+        let mut proceeds = match storage::read_sale_proceeds(&env, id) {
+            Some(proceeds) if proceeds.token != token => {
+                panic_with_error!(&env, Error::ProceedsTokenMismatch)
+            }
+            Some(proceeds) => proceeds,
+            None => SaleProceeds {
+                token: token.clone(),
+                remaining: 0,
+            },
+        };
+
+        token::Client::new(&env, &token).transfer(
+            &depositor,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        proceeds.remaining += amount;
+        storage::write_sale_proceeds(&env, id, &proceeds);
+
+        events::SaleProceedsDeposited {
+            property_id: id,
+            depositor,
+            token,
+            amount,
+            remaining: proceeds.remaining,
+        }
+        .publish(&env);
+    }
+
+    /// Burns `amount` of `holder`'s fractions of a sold property and pays out
+    /// their pro-rata share of the remaining proceeds. Returns the amount paid.
+    pub fn redeem(env: Env, holder: Address, id: u128, amount: i128) -> i128 {
+        holder.require_auth();
+        pause::require_property_not_paused(&env, id);
+
+        let property = storage::read_property(&env, id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::InvalidProperty));
+        lifecycle::require_status(&env, &property, PropertyStatus::Sold);
+        let mut proceeds = storage::read_sale_proceeds(&env, id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::ProceedsNotDeposited));
+
+        Self::_burn(&env, &holder, id, amount);
+
+        let payout = math::mul_div(
+            &env,
+            amount,
+            proceeds.remaining,
+            property.total_supply,
+            Rounding::Down,
+        );
+        proceeds.remaining -= payout;
+        storage::write_sale_proceeds(&env, id, &proceeds);
+
+        token::Client::new(&env, &proceeds.token).transfer(
+            &env.current_contract_address(),
+            &holder,
+            &payout,
+        );
+
+        events::FractionsRedeemed {
+            property_id: id,
+            holder,
+            amount,
+            token: proceeds.token,
+            payout,
+        }
+        .publish(&env);
+
+        payout
+    }
+
+    pub fn sale_proceeds(env: Env, id: u128) -> Option<SaleProceeds> {
+        storage::read_sale_proceeds(&env, id)
+    }
+}
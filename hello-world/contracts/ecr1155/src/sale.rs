@@ -0,0 +1,102 @@
+use soroban_sdk::{contractimpl, panic_with_error, token, Address, Env};
+
+use crate::{
+    events, lifecycle, math, pause, receiver, storage, Error, PropertyStatus,
+    RealEstateTokenContract, RealEstateTokenContractArgs, RealEstateTokenContractClient, Role,
+};
+
+pub(crate) fn apply_payment_token(env: &Env, token: Address, accepted: bool) {
+    storage::write_payment_token(env, &token, accepted);
+    events::PaymentTokenSet { token, accepted }.publish(env);
+}
+
+pub(crate) fn apply_treasury(env: &Env, treasury: Address) {
+    storage::write_treasury(env, &treasury);
+    events::TreasurySet { treasury }.publish(env);
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Admin-only. Timelocked. Allows or forbids `token` (a SEP-41 contract such
+    /// as a USDC or BRL stablecoin SAC) as payment in `buy_fractions`.
+    pub fn set_payment_token(env: Env, admin: Address, token: Address, accepted: bool) {
+        rbac::require_role(&env, &admin, Role::Admin);
+        rbac::timelock::require_unlocked(&env);
+        apply_payment_token(&env, token, accepted);
+    }
+
+    pub fn is_payment_token(env: Env, token: Address) -> bool {
+        storage::read_payment_token(&env, &token)
+    }
+
+    /// Admin-only. Timelocked. Where primary-sale proceeds are sent. Until one
+    /// is set they go straight to the property's builder.
+    pub fn set_treasury(env: Env, admin: Address, treasury: Address) {
+        rbac::require_role(&env, &admin, Role::Admin);
+        rbac::timelock::require_unlocked(&env);
+        apply_treasury(&env, treasury);
+    }
+
+    pub fn treasury(env: Env) -> Option<Address> {
+        storage::read_treasury(&env)
+    }
+
+    /// Buys `amount` fractions of a property in `Fundraising` from its
+    /// builder's inventory, paying in `payment_token`. Reverts if the cost
+    /// would exceed `max_cost`, protecting the investor against price changes
+    /// between quoting and submitting. Returns the amount paid.
+    pub fn buy_fractions(
+        env: Env,
+        investor: Address,
+        property_id: u128,
+        payment_token: Address,
+        amount: i128,
+        max_cost: i128,
+    ) -> i128 {
+        investor.require_auth();
+        pause::require_property_not_paused(&env, property_id);
+
+        let mut property = storage::read_property(&env, property_id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::InvalidProperty));
+        lifecycle::require_status(&env, &property, PropertyStatus::Fundraising);
+
+        if !storage::read_payment_token(&env, &payment_token) {
+            panic_with_error!(&env, Error::PaymentTokenNotAccepted);
+        }
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InsufficientBalance);
+        }
+
+        let cost = math::cost_of(&env, &property, amount);
+        if cost > max_cost {
+            panic_with_error!(&env, Error::MaxCostExceeded);
+        }
+
+        let treasury = storage::read_treasury(&env).unwrap_or(property.builder.clone());
+        token::Client::new(&env, &payment_token).transfer(&investor, &treasury, &cost);
+
+        Self::_transfer(&env, &property.builder, &investor, property_id, amount);
+        receiver::notify(
+            &env,
+            &investor,
+            &property.builder,
+            &investor,
+            property_id,
+            amount,
+        );
+
+        property.ele_tem += cost;
+        storage::write_property(&env, &property);
+
+        events::FractionsPurchased {
+            property_id,
+            investor,
+            amount,
+            payment_token,
+            cost,
+        }
+        .publish(&env);
+
+        cost
+    }
+}
@@ -0,0 +1,133 @@
+//! Point-in-time balances per property.
+//!
+//! Taking a snapshot only bumps the property's snapshot counter. The first time
+//! a balance (or the total supply) changes afterwards, its value from before the
+//! change is checkpointed under the current snapshot id. Reading a snapshot
+//! returns the first checkpoint at or after it, or the live value if nothing
+//! has changed since. Every balance and supply change must call
+//! [`update_balance`] / [`update_supply`] before writing.
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Vec};
+
+use crate::{
+    events, pause, storage, Error, RealEstateTokenContract, RealEstateTokenContractArgs,
+    RealEstateTokenContractClient, Role,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub snapshot_id: u32,
+    pub value: i128,
+}
+
+/// Appends `current` under `snapshot_id` unless that snapshot is already
+/// checkpointed. Returns whether anything changed.
+fn checkpoint(checkpoints: &mut Vec<Checkpoint>, snapshot_id: u32, current: i128) -> bool {
+    match checkpoints.last() {
+        Some(last) if last.snapshot_id >= snapshot_id => false,
+        _ => {
+            checkpoints.push_back(Checkpoint {
+                snapshot_id,
+                value: current,
+            });
+            true
+        }
+    }
+}
+
+/// Value of the first checkpoint taken at or after `snapshot_id`.
+fn lookup(checkpoints: &Vec<Checkpoint>, snapshot_id: u32) -> Option<i128> {
+    let (mut low, mut high) = (0, checkpoints.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        if checkpoints.get_unchecked(mid).snapshot_id < snapshot_id {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    checkpoints.get(low).map(|checkpoint| checkpoint.value)
+}
+
+fn require_snapshot(env: &Env, id: u128, snapshot_id: u32) {
+    if snapshot_id == 0 || snapshot_id > storage::read_snapshot_id(env, id) {
+        panic_with_error!(env, Error::InvalidSnapshot);
+    }
+}
+
+/// Checkpoints `holder`'s balance of `id` ahead of a change.
+pub(crate) fn update_balance(env: &Env, holder: &Address, id: u128) {
+    let snapshot_id = storage::read_snapshot_id(env, id);
+    if snapshot_id == 0 {
+        return;
+    }
+    let mut checkpoints = storage::read_balance_checkpoints(env, holder, id);
+    let current = storage::read_balance(env, holder, id);
+    if checkpoint(&mut checkpoints, snapshot_id, current) {
+        storage::write_balance_checkpoints(env, holder, id, &checkpoints);
+    }
+}
+
+/// Checkpoints the total supply of `id` ahead of a change.
+pub(crate) fn update_supply(env: &Env, id: u128) {
+    let snapshot_id = storage::read_snapshot_id(env, id);
+    if snapshot_id == 0 {
+        return;
+    }
+    let mut checkpoints = storage::read_supply_checkpoints(env, id);
+    let current = storage::read_property(env, id)
+        .map(|property| property.total_supply)
+        .unwrap_or(0);
+    if checkpoint(&mut checkpoints, snapshot_id, current) {
+        storage::write_supply_checkpoints(env, id, &checkpoints);
+    }
+}
+
+/// Opens a new snapshot of property `id` and returns its id.
+pub(crate) fn take(env: &Env, id: u128) -> u32 {
+    let snapshot_id = storage::read_snapshot_id(env, id) + 1;
+    storage::write_snapshot_id(env, id, snapshot_id);
+    events::SnapshotTaken {
+        property_id: id,
+        snapshot_id,
+        taken_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+    snapshot_id
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Records the current balances of property `id` for later reference.
+    /// Callable by the property's builder or an `Operator`.
+    pub fn snapshot(env: Env, caller: Address, property_id: u128) -> u32 {
+        caller.require_auth();
+        pause::require_property_not_paused(&env, property_id);
+
+        let property = storage::read_property(&env, property_id)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::InvalidProperty));
+        if caller != property.builder && !rbac::has_role(&env, Role::Operator, &caller) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+
+        take(&env, property_id)
+    }
+
+    /// Id of the most recent snapshot of property `id`, `0` if none was taken.
+    pub fn current_snapshot_id(env: Env, id: u128) -> u32 {
+        storage::read_snapshot_id(&env, id)
+    }
+
+    pub fn balance_of_at(env: Env, holder: Address, id: u128, snapshot_id: u32) -> i128 {
+        require_snapshot(&env, id, snapshot_id);
+        let checkpoints = storage::read_balance_checkpoints(&env, &holder, id);
+        lookup(&checkpoints, snapshot_id)
+            .unwrap_or_else(|| storage::read_balance(&env, &holder, id))
+    }
+
+    pub fn total_supply_at(env: Env, id: u128, snapshot_id: u32) -> i128 {
+        require_snapshot(&env, id, snapshot_id);
+        let checkpoints = storage::read_supply_checkpoints(&env, id);
+        lookup(&checkpoints, snapshot_id).unwrap_or_else(|| Self::total_supply(env.clone(), id))
+    }
+}
@@ -0,0 +1,862 @@
+use soroban_sdk::{contracttype, Address, Env, String, Vec};
+
+use crate::{
+    limits::Acquisition, Allowance, Builder, Checkpoint, GovernanceParams, HolderRewards,
+    HoldingLimits, Identity, InvestorCategory, PendingRecovery, Property, PropertyStatus, Proposal,
+    SaleProceeds, TransferRules, VestingSchedule,
+};
+
+const DAY_IN_LEDGERS: u32 = 17280;
+const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const PERSISTENT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// `Property` as stored in the pre-`DataKey` `"properties"` map, before it had
+/// a lifecycle status.
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacyProperty {
+    pub id: u128,
+    pub builder: Address,
+    pub name_property: String,
+    pub ele_quer: i128,
+    pub ele_tem: i128,
+    pub total_supply: i128,
+    pub nome_construtora: String,
+    pub ipfs: String,
+    pub sigla: String,
+}
+
+impl LegacyProperty {
+    /// Legacy properties were already open to investors, so they migrate as
+    /// `Fundraising`.
+    pub fn into_property(self, env: &Env) -> Property {
+        Property {
+            id: self.id,
+            builder: self.builder,
+            name_property: self.name_property,
+            ele_quer: self.ele_quer,
+            ele_tem: self.ele_tem,
+            total_supply: self.total_supply,
+            max_supply: None,
+            nome_construtora: self.nome_construtora,
+            ipfs: self.ipfs,
+            sigla: self.sigla,
+            status: PropertyStatus::Fundraising,
+            status_updated_at: env.ledger().timestamp(),
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextPropertyId,
+    Property(u128),
+    Balance(Address, u128),
+    OperatorApproval(Address, Address),
+    Allowance(Address, Address, u128),
+    Builder(Address),
+    Builders,
+    BuilderProperties(Address),
+    Paused,
+    PropertyPaused(u128),
+    HolderCount(u128),
+    HolderAt(u128, u32),
+    HolderIndex(u128, Address),
+    PortfolioCount(Address),
+    PortfolioAt(Address, u32),
+    PortfolioIndex(Address, u128),
+    Identity(Address),
+    TransferRules(u128),
+    PaymentToken(Address),
+    Treasury,
+    RewardToken(u128),
+    RewardPerFraction(u128),
+    HolderRewards(Address, u128),
+    SnapshotId(u128),
+    BalanceCheckpoints(Address, u128),
+    SupplyCheckpoints(u128),
+    GovernanceParams(u128),
+    NextProposalId,
+    Proposal(u64),
+    Vote(u64, Address),
+    SaleProceeds(u128),
+    FractionReceiver(Address),
+    Vesting(Address, u128),
+    HoldingLimits(u128),
+    AnnualCap(InvestorCategory),
+    InvestmentLog(Address),
+    RecoveryDelay,
+    NextRecoveryId,
+    PendingRecovery(u64),
+    Frozen(Address),
+    FrozenAmount(Address, u128),
+    SchemaVersion,
+}
+
+pub fn extend_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn extend_persistent(env: &Env, key: &DataKey) {
+    env.storage().persistent().extend_ttl(
+        key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+}
+
+pub fn read_next_property_id(env: &Env) -> u128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::NextPropertyId)
+        .unwrap_or(1)
+}
+
+pub fn write_next_property_id(env: &Env, id: u128) {
+    env.storage().instance().set(&DataKey::NextPropertyId, &id);
+}
+
+pub fn read_property(env: &Env, id: u128) -> Option<Property> {
+    let key = DataKey::Property(id);
+    let property = env.storage().persistent().get(&key);
+    if property.is_some() {
+        extend_persistent(env, &key);
+    }
+    property
+}
+
+pub fn write_property(env: &Env, property: &Property) {
+    let key = DataKey::Property(property.id);
+    env.storage().persistent().set(&key, property);
+    extend_persistent(env, &key);
+}
+
+pub fn read_balance(env: &Env, owner: &Address, id: u128) -> i128 {
+    let key = DataKey::Balance(owner.clone(), id);
+    match env.storage().persistent().get(&key) {
+        Some(balance) => {
+            extend_persistent(env, &key);
+            balance
+        }
+        None => 0,
+    }
+}
+
+/// Stores `owner`'s balance of `id`, dropping the entry entirely when it reaches zero.
+pub fn write_balance(env: &Env, owner: &Address, id: u128, amount: i128) {
+    let key = DataKey::Balance(owner.clone(), id);
+    if amount > 0 {
+        env.storage().persistent().set(&key, &amount);
+        extend_persistent(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn read_approval(env: &Env, owner: &Address, operator: &Address) -> bool {
+    let key = DataKey::OperatorApproval(owner.clone(), operator.clone());
+    match env.storage().persistent().get(&key) {
+        Some(approved) => {
+            extend_persistent(env, &key);
+            approved
+        }
+        None => false,
+    }
+}
+
+pub fn write_approval(env: &Env, owner: &Address, operator: &Address, approved: bool) {
+    let key = DataKey::OperatorApproval(owner.clone(), operator.clone());
+    if approved {
+        env.storage().persistent().set(&key, &true);
+        extend_persistent(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Returns the stored allowance, or `None` once it has expired.
+pub fn read_allowance(
+    env: &Env,
+    owner: &Address,
+    spender: &Address,
+    id: u128,
+) -> Option<Allowance> {
+    let key = DataKey::Allowance(owner.clone(), spender.clone(), id);
+    env.storage()
+        .temporary()
+        .get::<_, Allowance>(&key)
+        .filter(|allowance| allowance.expiration_ledger >= env.ledger().sequence())
+}
+
+/// Allowances live in temporary storage and are kept alive exactly until they expire.
+pub fn write_allowance(
+    env: &Env,
+    owner: &Address,
+    spender: &Address,
+    id: u128,
+    allowance: &Allowance,
+) {
+    let key = DataKey::Allowance(owner.clone(), spender.clone(), id);
+    if allowance.amount > 0 {
+        env.storage().temporary().set(&key, allowance);
+        let live_for = allowance
+            .expiration_ledger
+            .saturating_sub(env.ledger().sequence());
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, live_for, live_for);
+    } else {
+        env.storage().temporary().remove(&key);
+    }
+}
+
+pub fn read_builder(env: &Env, builder: &Address) -> Option<Builder> {
+    let key = DataKey::Builder(builder.clone());
+    let profile = env.storage().persistent().get(&key);
+    if profile.is_some() {
+        extend_persistent(env, &key);
+    }
+    profile
+}
+
+pub fn write_builder(env: &Env, profile: &Builder) {
+    let key = DataKey::Builder(profile.address.clone());
+    env.storage().persistent().set(&key, profile);
+    extend_persistent(env, &key);
+}
+
+pub fn remove_builder(env: &Env, builder: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Builder(builder.clone()));
+}
+
+pub fn read_builders(env: &Env) -> Vec<Address> {
+    let key = DataKey::Builders;
+    match env.storage().persistent().get(&key) {
+        Some(builders) => {
+            extend_persistent(env, &key);
+            builders
+        }
+        None => Vec::new(env),
+    }
+}
+
+pub fn write_builders(env: &Env, builders: &Vec<Address>) {
+    let key = DataKey::Builders;
+    env.storage().persistent().set(&key, builders);
+    extend_persistent(env, &key);
+}
+
+pub fn read_builder_properties(env: &Env, builder: &Address) -> Vec<u128> {
+    let key = DataKey::BuilderProperties(builder.clone());
+    match env.storage().persistent().get(&key) {
+        Some(ids) => {
+            extend_persistent(env, &key);
+            ids
+        }
+        None => Vec::new(env),
+    }
+}
+
+pub fn write_builder_properties(env: &Env, builder: &Address, ids: &Vec<u128>) {
+    let key = DataKey::BuilderProperties(builder.clone());
+    env.storage().persistent().set(&key, ids);
+    extend_persistent(env, &key);
+}
+
+/// Whether a pre-`DataKey` deployment has yet to finish
+/// `migrate_legacy_storage`. Its `initialize` always wrote `"admin"`, which
+/// only the last batch removes.
+pub fn has_legacy_storage(env: &Env) -> bool {
+    env.storage().instance().has(&"admin")
+}
+
+pub fn read_paused(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::Paused)
+        .unwrap_or(false)
+}
+
+pub fn write_paused(env: &Env, paused: bool) {
+    env.storage().instance().set(&DataKey::Paused, &paused);
+}
+
+pub fn read_property_paused(env: &Env, id: u128) -> bool {
+    let key = DataKey::PropertyPaused(id);
+    let paused = env.storage().persistent().has(&key);
+    if paused {
+        extend_persistent(env, &key);
+    }
+    paused
+}
+
+pub fn write_property_paused(env: &Env, id: u128, paused: bool) {
+    let key = DataKey::PropertyPaused(id);
+    if paused {
+        env.storage().persistent().set(&key, &true);
+        extend_persistent(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Number of distinct addresses with a non-zero balance of `id`.
+pub fn read_holder_count(env: &Env, id: u128) -> u32 {
+    let key = DataKey::HolderCount(id);
+    match env.storage().persistent().get(&key) {
+        Some(count) => {
+            extend_persistent(env, &key);
+            count
+        }
+        None => 0,
+    }
+}
+
+pub fn write_holder_count(env: &Env, id: u128, count: u32) {
+    let key = DataKey::HolderCount(id);
+    env.storage().persistent().set(&key, &count);
+    extend_persistent(env, &key);
+}
+
+pub fn read_holder_at(env: &Env, id: u128, index: u32) -> Option<Address> {
+    let key = DataKey::HolderAt(id, index);
+    let holder = env.storage().persistent().get(&key);
+    if holder.is_some() {
+        extend_persistent(env, &key);
+    }
+    holder
+}
+
+pub fn write_holder_at(env: &Env, id: u128, index: u32, holder: &Address) {
+    let key = DataKey::HolderAt(id, index);
+    env.storage().persistent().set(&key, holder);
+    extend_persistent(env, &key);
+}
+
+pub fn remove_holder_at(env: &Env, id: u128, index: u32) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::HolderAt(id, index));
+}
+
+pub fn read_holder_index(env: &Env, id: u128, holder: &Address) -> Option<u32> {
+    let key = DataKey::HolderIndex(id, holder.clone());
+    let index = env.storage().persistent().get(&key);
+    if index.is_some() {
+        extend_persistent(env, &key);
+    }
+    index
+}
+
+pub fn write_holder_index(env: &Env, id: u128, holder: &Address, index: u32) {
+    let key = DataKey::HolderIndex(id, holder.clone());
+    env.storage().persistent().set(&key, &index);
+    extend_persistent(env, &key);
+}
+
+pub fn remove_holder_index(env: &Env, id: u128, holder: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::HolderIndex(id, holder.clone()));
+}
+
+/// Number of properties `holder` has a non-zero balance of.
+pub fn read_portfolio_count(env: &Env, holder: &Address) -> u32 {
+    let key = DataKey::PortfolioCount(holder.clone());
+    match env.storage().persistent().get(&key) {
+        Some(count) => {
+            extend_persistent(env, &key);
+            count
+        }
+        None => 0,
+    }
+}
+
+pub fn write_portfolio_count(env: &Env, holder: &Address, count: u32) {
+    let key = DataKey::PortfolioCount(holder.clone());
+    env.storage().persistent().set(&key, &count);
+    extend_persistent(env, &key);
+}
+
+pub fn read_portfolio_at(env: &Env, holder: &Address, index: u32) -> Option<u128> {
+    let key = DataKey::PortfolioAt(holder.clone(), index);
+    let id = env.storage().persistent().get(&key);
+    if id.is_some() {
+        extend_persistent(env, &key);
+    }
+    id
+}
+
+pub fn write_portfolio_at(env: &Env, holder: &Address, index: u32, id: u128) {
+    let key = DataKey::PortfolioAt(holder.clone(), index);
+    env.storage().persistent().set(&key, &id);
+    extend_persistent(env, &key);
+}
+
+pub fn remove_portfolio_at(env: &Env, holder: &Address, index: u32) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PortfolioAt(holder.clone(), index));
+}
+
+pub fn read_portfolio_index(env: &Env, holder: &Address, id: u128) -> Option<u32> {
+    let key = DataKey::PortfolioIndex(holder.clone(), id);
+    let index = env.storage().persistent().get(&key);
+    if index.is_some() {
+        extend_persistent(env, &key);
+    }
+    index
+}
+
+pub fn write_portfolio_index(env: &Env, holder: &Address, id: u128, index: u32) {
+    let key = DataKey::PortfolioIndex(holder.clone(), id);
+    env.storage().persistent().set(&key, &index);
+    extend_persistent(env, &key);
+}
+
+pub fn remove_portfolio_index(env: &Env, holder: &Address, id: u128) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PortfolioIndex(holder.clone(), id));
+}
+
+pub fn read_identity(env: &Env, account: &Address) -> Option<Identity> {
+    let key = DataKey::Identity(account.clone());
+    let identity = env.storage().persistent().get(&key);
+    if identity.is_some() {
+        extend_persistent(env, &key);
+    }
+    identity
+}
+
+pub fn write_identity(env: &Env, account: &Address, identity: &Identity) {
+    let key = DataKey::Identity(account.clone());
+    env.storage().persistent().set(&key, identity);
+    extend_persistent(env, &key);
+}
+
+pub fn remove_identity(env: &Env, account: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Identity(account.clone()));
+}
+
+pub fn read_transfer_rules(env: &Env, id: u128) -> TransferRules {
+    let key = DataKey::TransferRules(id);
+    match env.storage().persistent().get(&key) {
+        Some(rules) => {
+            extend_persistent(env, &key);
+            rules
+        }
+        None => TransferRules::unrestricted(env),
+    }
+}
+
+pub fn write_transfer_rules(env: &Env, id: u128, rules: &TransferRules) {
+    let key = DataKey::TransferRules(id);
+    env.storage().persistent().set(&key, rules);
+    extend_persistent(env, &key);
+}
+
+pub fn read_payment_token(env: &Env, token: &Address) -> bool {
+    let key = DataKey::PaymentToken(token.clone());
+    let accepted = env.storage().persistent().has(&key);
+    if accepted {
+        extend_persistent(env, &key);
+    }
+    accepted
+}
+
+pub fn write_payment_token(env: &Env, token: &Address, accepted: bool) {
+    let key = DataKey::PaymentToken(token.clone());
+    if accepted {
+        env.storage().persistent().set(&key, &true);
+        extend_persistent(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn read_treasury(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Treasury)
+}
+
+pub fn write_treasury(env: &Env, treasury: &Address) {
+    env.storage().instance().set(&DataKey::Treasury, treasury);
+}
+
+pub fn read_reward_token(env: &Env, id: u128) -> Option<Address> {
+    let key = DataKey::RewardToken(id);
+    let token = env.storage().persistent().get(&key);
+    if token.is_some() {
+        extend_persistent(env, &key);
+    }
+    token
+}
+
+pub fn write_reward_token(env: &Env, id: u128, token: &Address) {
+    let key = DataKey::RewardToken(id);
+    env.storage().persistent().set(&key, token);
+    extend_persistent(env, &key);
+}
+
+pub fn read_reward_per_fraction(env: &Env, id: u128) -> i128 {
+    let key = DataKey::RewardPerFraction(id);
+    match env.storage().persistent().get(&key) {
+        Some(value) => {
+            extend_persistent(env, &key);
+            value
+        }
+        None => 0,
+    }
+}
+
+pub fn write_reward_per_fraction(env: &Env, id: u128, value: i128) {
+    let key = DataKey::RewardPerFraction(id);
+    env.storage().persistent().set(&key, &value);
+    extend_persistent(env, &key);
+}
+
+pub fn read_holder_rewards(env: &Env, holder: &Address, id: u128) -> HolderRewards {
+    let key = DataKey::HolderRewards(holder.clone(), id);
+    match env.storage().persistent().get(&key) {
+        Some(rewards) => {
+            extend_persistent(env, &key);
+            rewards
+        }
+        None => HolderRewards::default(),
+    }
+}
+
+pub fn write_holder_rewards(env: &Env, holder: &Address, id: u128, rewards: &HolderRewards) {
+    let key = DataKey::HolderRewards(holder.clone(), id);
+    env.storage().persistent().set(&key, rewards);
+    extend_persistent(env, &key);
+}
+
+pub fn read_snapshot_id(env: &Env, id: u128) -> u32 {
+    let key = DataKey::SnapshotId(id);
+    match env.storage().persistent().get(&key) {
+        Some(snapshot_id) => {
+            extend_persistent(env, &key);
+            snapshot_id
+        }
+        None => 0,
+    }
+}
+
+pub fn write_snapshot_id(env: &Env, id: u128, snapshot_id: u32) {
+    let key = DataKey::SnapshotId(id);
+    env.storage().persistent().set(&key, &snapshot_id);
+    extend_persistent(env, &key);
+}
+
+pub fn read_balance_checkpoints(env: &Env, holder: &Address, id: u128) -> Vec<Checkpoint> {
+    let key = DataKey::BalanceCheckpoints(holder.clone(), id);
+    match env.storage().persistent().get(&key) {
+        Some(checkpoints) => {
+            extend_persistent(env, &key);
+            checkpoints
+        }
+        None => Vec::new(env),
+    }
+}
+
+pub fn write_balance_checkpoints(
+    env: &Env,
+    holder: &Address,
+    id: u128,
+    checkpoints: &Vec<Checkpoint>,
+) {
+    let key = DataKey::BalanceCheckpoints(holder.clone(), id);
+    env.storage().persistent().set(&key, checkpoints);
+    extend_persistent(env, &key);
+}
+
+pub fn read_supply_checkpoints(env: &Env, id: u128) -> Vec<Checkpoint> {
+    let key = DataKey::SupplyCheckpoints(id);
+    match env.storage().persistent().get(&key) {
+        Some(checkpoints) => {
+            extend_persistent(env, &key);
+            checkpoints
+        }
+        None => Vec::new(env),
+    }
+}
+
+pub fn write_supply_checkpoints(env: &Env, id: u128, checkpoints: &Vec<Checkpoint>) {
+    let key = DataKey::SupplyCheckpoints(id);
+    env.storage().persistent().set(&key, checkpoints);
+    extend_persistent(env, &key);
+}
+
+pub fn read_governance_params(env: &Env, id: u128) -> GovernanceParams {
+    let key = DataKey::GovernanceParams(id);
+    match env.storage().persistent().get(&key) {
+        Some(params) => {
+            extend_persistent(env, &key);
+            params
+        }
+        None => GovernanceParams::default(),
+    }
+}
+
+pub fn write_governance_params(env: &Env, id: u128, params: &GovernanceParams) {
+    let key = DataKey::GovernanceParams(id);
+    env.storage().persistent().set(&key, params);
+    extend_persistent(env, &key);
+}
+
+pub fn read_next_proposal_id(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::NextProposalId)
+        .unwrap_or(1)
+}
+
+pub fn write_next_proposal_id(env: &Env, id: u64) {
+    env.storage().instance().set(&DataKey::NextProposalId, &id);
+}
+
+pub fn read_proposal(env: &Env, id: u64) -> Option<Proposal> {
+    let key = DataKey::Proposal(id);
+    let proposal = env.storage().persistent().get(&key);
+    if proposal.is_some() {
+        extend_persistent(env, &key);
+    }
+    proposal
+}
+
+pub fn write_proposal(env: &Env, proposal: &Proposal) {
+    let key = DataKey::Proposal(proposal.id);
+    env.storage().persistent().set(&key, proposal);
+    extend_persistent(env, &key);
+}
+
+pub fn read_vote(env: &Env, proposal_id: u64, voter: &Address) -> Option<u32> {
+    let key = DataKey::Vote(proposal_id, voter.clone());
+    let option = env.storage().persistent().get(&key);
+    if option.is_some() {
+        extend_persistent(env, &key);
+    }
+    option
+}
+
+pub fn write_vote(env: &Env, proposal_id: u64, voter: &Address, option: u32) {
+    let key = DataKey::Vote(proposal_id, voter.clone());
+    env.storage().persistent().set(&key, &option);
+    extend_persistent(env, &key);
+}
+
+pub fn read_sale_proceeds(env: &Env, id: u128) -> Option<SaleProceeds> {
+    let key = DataKey::SaleProceeds(id);
+    let proceeds = env.storage().persistent().get(&key);
+    if proceeds.is_some() {
+        extend_persistent(env, &key);
+    }
+    proceeds
+}
+
+pub fn write_sale_proceeds(env: &Env, id: u128, proceeds: &SaleProceeds) {
+    let key = DataKey::SaleProceeds(id);
+    env.storage().persistent().set(&key, proceeds);
+    extend_persistent(env, &key);
+}
+
+pub fn read_fraction_receiver(env: &Env, receiver: &Address) -> bool {
+    let key = DataKey::FractionReceiver(receiver.clone());
+    let enabled = env.storage().persistent().has(&key);
+    if enabled {
+        extend_persistent(env, &key);
+    }
+    enabled
+}
+
+pub fn write_fraction_receiver(env: &Env, receiver: &Address, enabled: bool) {
+    let key = DataKey::FractionReceiver(receiver.clone());
+    if enabled {
+        env.storage().persistent().set(&key, &());
+        extend_persistent(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn read_vesting(env: &Env, holder: &Address, id: u128) -> Option<VestingSchedule> {
+    let key = DataKey::Vesting(holder.clone(), id);
+    let schedule = env.storage().persistent().get(&key);
+    if schedule.is_some() {
+        extend_persistent(env, &key);
+    }
+    schedule
+}
+
+pub fn write_vesting(env: &Env, holder: &Address, id: u128, schedule: &VestingSchedule) {
+    let key = DataKey::Vesting(holder.clone(), id);
+    env.storage().persistent().set(&key, schedule);
+    extend_persistent(env, &key);
+}
+
+pub fn remove_vesting(env: &Env, holder: &Address, id: u128) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Vesting(holder.clone(), id));
+}
+
+pub fn read_holding_limits(env: &Env, id: u128) -> HoldingLimits {
+    let key = DataKey::HoldingLimits(id);
+    match env.storage().persistent().get(&key) {
+        Some(limits) => {
+            extend_persistent(env, &key);
+            limits
+        }
+        None => HoldingLimits::default(),
+    }
+}
+
+pub fn write_holding_limits(env: &Env, id: u128, limits: &HoldingLimits) {
+    let key = DataKey::HoldingLimits(id);
+    env.storage().persistent().set(&key, limits);
+    extend_persistent(env, &key);
+}
+
+pub fn read_annual_cap(env: &Env, category: InvestorCategory) -> i128 {
+    let key = DataKey::AnnualCap(category);
+    match env.storage().persistent().get(&key) {
+        Some(cap) => {
+            extend_persistent(env, &key);
+            cap
+        }
+        None => 0,
+    }
+}
+
+pub fn write_annual_cap(env: &Env, category: InvestorCategory, cap: i128) {
+    let key = DataKey::AnnualCap(category);
+    env.storage().persistent().set(&key, &cap);
+    extend_persistent(env, &key);
+}
+
+pub fn read_acquisitions(env: &Env, investor: &Address) -> Vec<Acquisition> {
+    let key = DataKey::InvestmentLog(investor.clone());
+    match env.storage().persistent().get(&key) {
+        Some(acquisitions) => {
+            extend_persistent(env, &key);
+            acquisitions
+        }
+        None => Vec::new(env),
+    }
+}
+
+pub fn write_acquisitions(env: &Env, investor: &Address, acquisitions: &Vec<Acquisition>) {
+    let key = DataKey::InvestmentLog(investor.clone());
+    env.storage().persistent().set(&key, acquisitions);
+    extend_persistent(env, &key);
+}
+
+pub fn read_recovery_delay(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::RecoveryDelay)
+        .unwrap_or(0)
+}
+
+pub fn write_recovery_delay(env: &Env, delay: u64) {
+    env.storage()
+        .instance()
+        .set(&DataKey::RecoveryDelay, &delay);
+}
+
+pub fn read_next_recovery_id(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::NextRecoveryId)
+        .unwrap_or(1)
+}
+
+pub fn write_next_recovery_id(env: &Env, id: u64) {
+    env.storage().instance().set(&DataKey::NextRecoveryId, &id);
+}
+
+pub fn read_pending_recovery(env: &Env, id: u64) -> Option<PendingRecovery> {
+    let key = DataKey::PendingRecovery(id);
+    let pending = env.storage().persistent().get(&key);
+    if pending.is_some() {
+        extend_persistent(env, &key);
+    }
+    pending
+}
+
+pub fn write_pending_recovery(env: &Env, pending: &PendingRecovery) {
+    let key = DataKey::PendingRecovery(pending.id);
+    env.storage().persistent().set(&key, pending);
+    extend_persistent(env, &key);
+}
+
+pub fn remove_pending_recovery(env: &Env, id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PendingRecovery(id));
+}
+
+pub fn read_frozen(env: &Env, account: &Address) -> bool {
+    let key = DataKey::Frozen(account.clone());
+    let frozen = env.storage().persistent().has(&key);
+    if frozen {
+        extend_persistent(env, &key);
+    }
+    frozen
+}
+
+pub fn write_frozen(env: &Env, account: &Address, frozen: bool) {
+    let key = DataKey::Frozen(account.clone());
+    if frozen {
+        env.storage().persistent().set(&key, &());
+        extend_persistent(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn read_frozen_amount(env: &Env, holder: &Address, id: u128) -> i128 {
+    let key = DataKey::FrozenAmount(holder.clone(), id);
+    match env.storage().persistent().get(&key) {
+        Some(amount) => {
+            extend_persistent(env, &key);
+            amount
+        }
+        None => 0,
+    }
+}
+
+pub fn write_frozen_amount(env: &Env, holder: &Address, id: u128, amount: i128) {
+    let key = DataKey::FrozenAmount(holder.clone(), id);
+    if amount > 0 {
+        env.storage().persistent().set(&key, &amount);
+        extend_persistent(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Layout version of stored entries. Deployments from before it was stored
+/// are version 1.
+pub fn read_schema_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::SchemaVersion)
+        .unwrap_or(1)
+}
+
+pub fn write_schema_version(env: &Env, version: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::SchemaVersion, &version);
+}
@@ -1,21 +1,1444 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{vec, Env, String};
+use crate::testutils::upload_empty_wasm;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Bytes, BytesN, Env, IntoVal, String, Symbol,
+};
+
+fn setup(env: &Env) -> (RealEstateTokenContractClient<'_>, Address, Address) {
+    env.mock_all_auths();
+
+    let contract_id = env.register(RealEstateTokenContract, ());
+    let client = RealEstateTokenContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+
+    let builder = add_builder(env, &client, &admin);
+
+    (client, admin, builder)
+}
+
+fn add_builder(env: &Env, client: &RealEstateTokenContractClient, admin: &Address) -> Address {
+    let builder = Address::generate(env);
+    client.add_builder(
+        admin,
+        &builder,
+        &String::from_str(env, "Construtora Meridian"),
+        &BytesN::from_array(env, &[7; 32]),
+        &String::from_str(env, "ipfs://meridian-docs"),
+    );
+    builder
+}
+
+fn register(
+    env: &Env,
+    client: &RealEstateTokenContractClient,
+    builder: &Address,
+    supply: i128,
+) -> u128 {
+    client.register_property(
+        builder,
+        &String::from_str(env, "Edificio Aurora"),
+        &1_000_000,
+        &0,
+        &supply,
+        &String::from_str(env, "Construtora Meridian"),
+        &String::from_str(env, "ipfs://aurora"),
+        &String::from_str(env, "AUR"),
+    )
+}
+
+#[test]
+fn test_balance_of_and_total_supply() {
+    let env = Env::default();
+    let (client, _, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+
+    let investor = Address::generate(&env);
+    client.transfer_property(&builder, &investor, &id, &250);
+
+    assert_eq!(client.balance_of(&builder, &id), 750);
+    assert_eq!(client.balance_of(&investor, &id), 250);
+    assert_eq!(client.total_supply(&id), 1_000);
+    assert_eq!(
+        client.balance_of_batch(
+            &vec![&env, builder.clone(), investor.clone()],
+            &vec![&env, id, id]
+        ),
+        vec![&env, 750, 250]
+    );
+}
+
+#[test]
+fn test_batch_transfer() {
+    let env = Env::default();
+    let (client, _, builder) = setup(&env);
+    let first = register(&env, &client, &builder, 1_000);
+    let second = register(&env, &client, &builder, 500);
+
+    let investor = Address::generate(&env);
+    client.batch_transfer(
+        &builder,
+        &investor,
+        &vec![&env, first, second],
+        &vec![&env, 100, 500],
+    );
+
+    assert_eq!(client.balance_of(&investor, &first), 100);
+    assert_eq!(client.balance_of(&investor, &second), 500);
+    assert_eq!(client.balance_of(&builder, &second), 0);
+}
+
+#[test]
+fn test_batch_transfer_is_atomic() {
+    let env = Env::default();
+    let (client, _, builder) = setup(&env);
+    let first = register(&env, &client, &builder, 1_000);
+    let second = register(&env, &client, &builder, 500);
+
+    let investor = Address::generate(&env);
+    let result = client.try_batch_transfer(
+        &builder,
+        &investor,
+        &vec![&env, first, second],
+        &vec![&env, 100, 501],
+    );
+
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance.into())));
+    assert_eq!(client.balance_of(&investor, &first), 0);
+    assert_eq!(client.balance_of(&builder, &first), 1_000);
+}
+
+#[test]
+fn test_batch_length_mismatch() {
+    let env = Env::default();
+    let (client, _, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+
+    let result = client.try_balance_of_batch(&vec![&env, builder.clone()], &vec![&env, id, id]);
+    assert_eq!(result, Err(Ok(Error::LengthMismatch.into())));
+}
+
+#[test]
+fn test_operator_transfer_from() {
+    let env = Env::default();
+    let (client, _, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+
+    let operator = Address::generate(&env);
+    let investor = Address::generate(&env);
+    assert!(!client.is_approved_for_all(&builder, &operator));
+
+    client.set_approval_for_all(&builder, &operator, &true);
+    assert!(client.is_approved_for_all(&builder, &operator));
+    client.transfer_from(&operator, &builder, &investor, &id, &300);
+    assert_eq!(client.balance_of(&investor, &id), 300);
+
+    client.set_approval_for_all(&builder, &operator, &false);
+    let result = client.try_transfer_from(&operator, &builder, &investor, &id, &1);
+    assert_eq!(result, Err(Ok(Error::InsufficientAllowance.into())));
+}
+
+#[test]
+fn test_allowance_transfer_from() {
+    let env = Env::default();
+    let (client, _, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+
+    let spender = Address::generate(&env);
+    let investor = Address::generate(&env);
+    client.approve(&builder, &spender, &id, &200, &100);
+    assert_eq!(client.allowance(&builder, &spender, &id), 200);
+
+    client.transfer_from(&spender, &builder, &investor, &id, &150);
+    assert_eq!(client.allowance(&builder, &spender, &id), 50);
+    assert_eq!(client.balance_of(&investor, &id), 150);
+
+    let result = client.try_transfer_from(&spender, &builder, &investor, &id, &51);
+    assert_eq!(result, Err(Ok(Error::InsufficientAllowance.into())));
+}
+
+#[test]
+fn test_allowance_expires() {
+    let env = Env::default();
+    let (client, _, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+
+    let spender = Address::generate(&env);
+    client.approve(&builder, &spender, &id, &200, &10);
+    env.ledger().with_mut(|li| li.sequence_number = 11);
+
+    assert_eq!(client.allowance(&builder, &spender, &id), 0);
+    let result = client.try_transfer_from(&spender, &builder, &spender, &id, &1);
+    assert_eq!(result, Err(Ok(Error::InsufficientAllowance.into())));
+
+    let result = client.try_approve(&builder, &spender, &id, &200, &10);
+    assert_eq!(result, Err(Ok(Error::InvalidExpiration.into())));
+}
+
+#[test]
+fn test_migrate_legacy_storage() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(RealEstateTokenContract, ());
+    let client = RealEstateTokenContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+
+    let property = storage::LegacyProperty {
+        id: 1,
+        builder: admin.clone(),
+        name_property: String::from_str(&env, "Edificio Aurora"),
+        ele_quer: 1_000_000,
+        ele_tem: 0,
+        total_supply: 1_000,
+        nome_construtora: String::from_str(&env, "Construtora Meridian"),
+        ipfs: String::from_str(&env, "ipfs://aurora"),
+        sigla: String::from_str(&env, "AUR"),
+    };
+    env.as_contract(&contract_id, || {
+        let instance = env.storage().instance();
+        let mut properties = Map::new(&env);
+        properties.set(1u128, property.clone());
+        properties.set(
+            2u128,
+            storage::LegacyProperty {
+                id: 2,
+                ..property.clone()
+            },
+        );
+        let mut balances = Map::new(&env);
+        balances.set((admin.clone(), 1u128), 600i128);
+        balances.set((investor.clone(), 1u128), 400i128);
+        instance.set(&"admin", &admin);
+        instance.set(&"next_property_id", &3u128);
+        instance.set(&"properties", &properties);
+        instance.set(&"balances", &balances);
+    });
+
+    // The legacy deployment is initialized even though rbac has no admin yet.
+    let result = client.try_initialize(&investor);
+    assert_eq!(result, Err(Ok(Error::MigrationPending.into())));
+
+    // Two properties, then the two balances.
+    assert_eq!(client.migrate_legacy_storage(&0, &2), Some(2));
+    assert_eq!(client.balance_of(&investor, &1), 0);
+    // Balances still to migrate must not move in between batches.
+    let result = client.try_transfer_property(&admin, &investor, &1, &1);
+    assert_eq!(result, Err(Ok(Error::MigrationPending.into())));
+    assert_eq!(client.migrate_legacy_storage(&2, &2), None);
+
+    assert_eq!(client.builder_properties(&admin), vec![&env, 1, 2]);
+    let migrated = client.get_property(&1);
+    assert_eq!(migrated.name_property, property.name_property);
+    assert_eq!(migrated.total_supply, 1_000);
+    assert_eq!(migrated.status, PropertyStatus::Fundraising);
+    assert_eq!(client.balance_of(&admin, &1), 600);
+    assert_eq!(client.balance_of(&investor, &1), 400);
+    let builder = add_builder(&env, &client, &admin);
+    assert_eq!(register(&env, &client, &builder, 10), 3);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&"balances"));
+    });
+
+    let result = client.try_migrate_legacy_storage(&0, &2);
+    assert_eq!(result, Err(Ok(Error::AlreadyMigrated.into())));
+}
+
+#[test]
+fn test_builder_registry() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let other = add_builder(&env, &client, &admin);
+
+    assert_eq!(
+        client.list_builders(),
+        vec![&env, builder.clone(), other.clone()]
+    );
+    assert!(client.is_active_builder(&builder));
+
+    let first = register(&env, &client, &builder, 1_000);
+    let second = register(&env, &client, &builder, 500);
+    assert_eq!(
+        client.builder_properties(&builder),
+        vec![&env, first, second]
+    );
+    assert_eq!(client.get_property(&first).builder, builder);
+
+    client.suspend_builder(&admin, &other);
+    assert_eq!(client.get_builder(&other).status, BuilderStatus::Suspended);
+    let result = client.try_register_property(
+        &other,
+        &String::from_str(&env, "Edificio Boreal"),
+        &1_000_000,
+        &0,
+        &100,
+        &String::from_str(&env, "Construtora Meridian"),
+        &String::from_str(&env, "ipfs://boreal"),
+        &String::from_str(&env, "BOR"),
+    );
+    assert_eq!(result, Err(Ok(Error::BuilderNotActive.into())));
+
+    client.remove_builder(&admin, &other);
+    assert_eq!(client.list_builders(), vec![&env, builder.clone()]);
+    assert_eq!(
+        client.try_get_builder(&other),
+        Err(Ok(Error::BuilderNotFound.into()))
+    );
+}
+
+#[test]
+fn test_register_requires_builder() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+
+    let result = client.try_register_property(
+        &admin,
+        &String::from_str(&env, "Edificio Aurora"),
+        &1_000_000,
+        &0,
+        &100,
+        &String::from_str(&env, "Construtora Meridian"),
+        &String::from_str(&env, "ipfs://aurora"),
+        &String::from_str(&env, "AUR"),
+    );
+    assert_eq!(result, Err(Ok(Error::Unauthorized.into())));
+
+    let result = client.try_register_property(
+        &builder,
+        &String::from_str(&env, "Edificio Aurora"),
+        &1_000_000,
+        &0,
+        &100,
+        &String::from_str(&env, "Outra Construtora"),
+        &String::from_str(&env, "ipfs://aurora"),
+        &String::from_str(&env, "AUR"),
+    );
+    assert_eq!(result, Err(Ok(Error::BuilderNameMismatch.into())));
+}
+
+#[test]
+fn test_roles() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let officer = Address::generate(&env);
+
+    assert!(client.has_role(&Role::Admin, &admin));
+    assert!(!client.has_role(&Role::Compliance, &officer));
+
+    client.grant_role(&admin, &Role::Compliance, &officer);
+    assert!(client.has_role(&Role::Compliance, &officer));
+
+    client.renounce_role(&officer, &Role::Compliance);
+    assert!(!client.has_role(&Role::Compliance, &officer));
+
+    let result = client.try_grant_role(&builder, &Role::Admin, &builder);
+    assert_eq!(result, Err(Ok(rbac::Error::MissingRole.into())));
+
+    let result = client.try_add_builder(
+        &builder,
+        &officer,
+        &String::from_str(&env, "Construtora Meridian"),
+        &BytesN::from_array(&env, &[7; 32]),
+        &String::from_str(&env, "ipfs://meridian-docs"),
+    );
+    assert_eq!(result, Err(Ok(rbac::Error::MissingRole.into())));
+}
 
 #[test]
-fn test() {
+fn test_pause() {
     let env = Env::default();
-    let contract_id = env.register(Contract, ());
-    let client = ContractClient::new(&env, &contract_id);
+    let (client, admin, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+    let investor = Address::generate(&env);
+
+    let result = client.try_pause(&admin);
+    assert_eq!(result, Err(Ok(rbac::Error::MissingRole.into())));
+
+    client.grant_role(&admin, &Role::Pauser, &admin);
+    client.pause(&admin);
+    assert!(client.is_paused());
+    let result = client.try_transfer_property(&builder, &investor, &id, &1);
+    assert_eq!(result, Err(Ok(Error::ContractPaused.into())));
+    let result = client.try_register_property(
+        &builder,
+        &String::from_str(&env, "Edificio Boreal"),
+        &1_000_000,
+        &0,
+        &100,
+        &String::from_str(&env, "Construtora Meridian"),
+        &String::from_str(&env, "ipfs://boreal"),
+        &String::from_str(&env, "BOR"),
+    );
+    assert_eq!(result, Err(Ok(Error::ContractPaused.into())));
+    let result = client.try_snapshot(&builder, &id);
+    assert_eq!(result, Err(Ok(Error::ContractPaused.into())));
+
+    client.unpause(&admin);
+    client.transfer_property(&builder, &investor, &id, &1);
+    assert_eq!(client.balance_of(&investor, &id), 1);
+}
 
-    let words = client.hello(&String::from_str(&env, "Dev"));
+#[test]
+fn test_pause_property() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let disputed = register(&env, &client, &builder, 1_000);
+    let other = register(&env, &client, &builder, 1_000);
+    let investor = Address::generate(&env);
+
+    client.grant_role(&admin, &Role::Pauser, &admin);
+    client.pause_property(&admin, &disputed);
+    assert!(client.is_property_paused(&disputed));
+
+    let result = client.try_batch_transfer(
+        &builder,
+        &investor,
+        &vec![&env, other, disputed],
+        &vec![&env, 10, 10],
+    );
+    assert_eq!(result, Err(Ok(Error::PropertyPaused.into())));
+    client.transfer_property(&builder, &investor, &other, &10);
+    let result = client.try_set_property_status(&builder, &disputed, &PropertyStatus::Fundraising);
+    assert_eq!(result, Err(Ok(Error::PropertyPaused.into())));
+    let result = client.try_snapshot(&builder, &disputed);
+    assert_eq!(result, Err(Ok(Error::PropertyPaused.into())));
+    assert_eq!(client.snapshot(&builder, &other), 1);
+
+    client.unpause_property(&admin, &disputed);
+    client.transfer_property(&builder, &investor, &disputed, &10);
+    assert_eq!(client.balance_of(&investor, &disputed), 10);
+}
+
+#[test]
+fn test_kyc_transfer_rules() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+    let officer = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let foreigner = Address::generate(&env);
+    let br = Symbol::new(&env, "BR");
+
+    client.grant_role(&admin, &Role::Compliance, &officer);
+    client.set_transfer_rules(
+        &officer,
+        &id,
+        &TransferRules {
+            kyc_required: true,
+            allowed_jurisdictions: vec![&env, br.clone()],
+            max_holders: 0,
+        },
+    );
+
+    assert_eq!(client.can_transfer(&builder, &investor, &id, &10), 1015);
+    client.register_identity(&officer, &builder, &br, &InvestorCategory::Professional);
+    assert_eq!(client.can_transfer(&builder, &investor, &id, &10), 1016);
+    let result = client.try_transfer_property(&builder, &investor, &id, &10);
+    assert_eq!(result, Err(Ok(Error::RecipientNotVerified.into())));
+
+    client.register_identity(&officer, &investor, &br, &InvestorCategory::Retail);
+    client.register_identity(
+        &officer,
+        &foreigner,
+        &Symbol::new(&env, "US"),
+        &InvestorCategory::Retail,
+    );
+    assert_eq!(client.can_transfer(&builder, &investor, &id, &10), 0);
+    assert_eq!(client.can_transfer(&builder, &foreigner, &id, &10), 1017);
+    assert_eq!(client.can_transfer(&builder, &investor, &id, &1_001), 1002);
+
+    client.transfer_property(&builder, &investor, &id, &10);
+    assert_eq!(client.balance_of(&investor, &id), 10);
+
+    client.remove_identity(&officer, &investor);
+    assert!(!client.is_verified(&investor));
+    assert_eq!(client.can_transfer(&investor, &builder, &id, &10), 1015);
+}
+
+#[test]
+fn test_holder_cap() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+
+    client.grant_role(&admin, &Role::Compliance, &admin);
+    client.set_transfer_rules(
+        &admin,
+        &id,
+        &TransferRules {
+            kyc_required: false,
+            allowed_jurisdictions: vec![&env],
+            max_holders: 2,
+        },
+    );
+
+    client.transfer_property(&builder, &first, &id, &10);
+    assert_eq!(client.can_transfer(&builder, &second, &id, &10), 1018);
+    // Moving a whole balance to a new holder keeps the count unchanged.
+    assert_eq!(client.can_transfer(&first, &second, &id, &10), 0);
+    client.transfer_property(&first, &second, &id, &10);
+    assert_eq!(client.balance_of(&second, &id), 10);
+}
+
+#[test]
+fn test_property_lifecycle() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+    let investor = Address::generate(&env);
+    assert_eq!(client.get_property(&id).status, PropertyStatus::Draft);
+
+    let result = client.try_set_property_status(&builder, &id, &PropertyStatus::Delivered);
+    assert_eq!(result, Err(Ok(Error::InvalidStatusTransition.into())));
+    let result = client.try_set_property_status(&investor, &id, &PropertyStatus::Fundraising);
+    assert_eq!(result, Err(Ok(Error::Unauthorized.into())));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.set_property_status(&builder, &id, &PropertyStatus::Fundraising);
+    let property = client.get_property(&id);
+    assert_eq!(property.status, PropertyStatus::Fundraising);
+    assert_eq!(property.status_updated_at, 1_000);
+
+    client.grant_role(&admin, &Role::Operator, &admin);
+    client.set_property_status(&admin, &id, &PropertyStatus::Cancelled);
+    assert_eq!(client.can_transfer(&builder, &investor, &id, &10), 1021);
+
+    let result = client.try_set_property_status(&builder, &id, &PropertyStatus::Fundraising);
+    assert_eq!(result, Err(Ok(Error::InvalidStatusTransition.into())));
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> (Address, StellarAssetClient<'a>) {
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    let address = sac.address();
+    (address.clone(), StellarAssetClient::new(env, &address))
+}
+
+#[test]
+fn test_buy_fractions() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 3_000);
+    let investor = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let (usdc, usdc_admin) = create_token(&env, &admin);
+    usdc_admin.mint(&investor, &1_000_000);
+
+    client.set_payment_token(&admin, &usdc, &true);
+    let result = client.try_buy_fractions(&investor, &id, &usdc, &30, &10_000);
+    assert_eq!(result, Err(Ok(Error::InvalidPropertyStatus.into())));
+
+    client.set_property_status(&builder, &id, &PropertyStatus::Fundraising);
+
+    // 1_000_000 / 3_000 = 333.33.. per fraction, rounded up on the total.
+    let result = client.try_buy_fractions(&investor, &id, &usdc, &30, &9_999);
+    assert_eq!(result, Err(Ok(Error::MaxCostExceeded.into())));
+    assert_eq!(
+        client.buy_fractions(&investor, &id, &usdc, &30, &10_000),
+        10_000
+    );
+
+    let usdc_client = TokenClient::new(&env, &usdc);
+    assert_eq!(usdc_client.balance(&builder), 10_000);
+    assert_eq!(client.balance_of(&investor, &id), 30);
+    assert_eq!(client.balance_of(&builder, &id), 2_970);
+    assert_eq!(client.get_property(&id).ele_tem, 10_000);
+
+    client.set_treasury(&admin, &treasury);
+    assert_eq!(client.buy_fractions(&investor, &id, &usdc, &1, &334), 334);
+    assert_eq!(usdc_client.balance(&treasury), 334);
+
+    let (other, _) = create_token(&env, &admin);
+    let result = client.try_buy_fractions(&investor, &id, &other, &1, &334);
+    assert_eq!(result, Err(Ok(Error::PaymentTokenNotAccepted.into())));
+}
+
+#[test]
+fn test_quotes() {
+    let env = Env::default();
+    let (client, _, builder) = setup(&env);
+    // Target of 1_000_000 split into 3_000 fractions, 250_000 raised.
+    let id = client.register_property(
+        &builder,
+        &String::from_str(&env, "Edificio Aurora"),
+        &1_000_000,
+        &250_000,
+        &3_000,
+        &String::from_str(&env, "Construtora Meridian"),
+        &String::from_str(&env, "ipfs://aurora"),
+        &String::from_str(&env, "AUR"),
+    );
+    let property = client.get_property(&id);
+
+    assert_eq!(client.decimals(), 7);
+    assert_eq!(client.quote_price(&id), 334);
+    assert_eq!(client.price(&property), 334);
+    assert_eq!(client.quote_funding_bps(&id), 2_500);
+    assert_eq!(client.percentual(&property), 2_500);
+
+    // 1_000 buys 3 fractions (3 * 333.33.. = 1_000 exactly).
     assert_eq!(
-        words,
-        vec![
-            &env,
-            String::from_str(&env, "Hello"),
-            String::from_str(&env, "Dev"),
-        ]
+        client.quote_purchase(&id, &1_000),
+        PurchaseQuote {
+            fractions: 3,
+            cost: 1_000,
+            change: 0
+        }
     );
+    assert_eq!(
+        client.quote_purchase(&id, &1_500),
+        PurchaseQuote {
+            fractions: 4,
+            cost: 1_334,
+            change: 166
+        }
+    );
+    assert_eq!(client.balance(&1_500, &property), 4);
+}
+
+#[test]
+fn test_income_distribution() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+    let investor = Address::generate(&env);
+    let late = Address::generate(&env);
+    let (brl, brl_admin) = create_token(&env, &admin);
+    let brl_client = TokenClient::new(&env, &brl);
+    brl_admin.mint(&builder, &1_000_000);
+
+    client.transfer_property(&builder, &investor, &id, &250);
+    client.deposit_income(&builder, &id, &brl, &10_000);
+    assert_eq!(client.claimable(&investor, &id), 2_500);
+    assert_eq!(client.claimable(&builder, &id), 7_500);
+
+    // Income earned before a transfer stays with the sender.
+    client.transfer_property(&investor, &late, &id, &250);
+    assert_eq!(client.claimable(&investor, &id), 2_500);
+    assert_eq!(client.claimable(&late, &id), 0);
+
+    client.deposit_income(&builder, &id, &brl, &4_000);
+    assert_eq!(client.claimable(&late, &id), 1_000);
+
+    assert_eq!(client.claim(&investor, &vec![&env, id]), vec![&env, 2_500]);
+    assert_eq!(brl_client.balance(&investor), 2_500);
+    assert_eq!(client.claimable(&investor, &id), 0);
+    assert_eq!(client.claim(&investor, &vec![&env, id]), vec![&env, 0]);
+
+    let (other, _) = create_token(&env, &admin);
+    let result = client.try_deposit_income(&builder, &id, &other, &1);
+    assert_eq!(result, Err(Ok(Error::RewardTokenMismatch.into())));
+    let result = client.try_deposit_income(&investor, &id, &brl, &1);
+    assert_eq!(result, Err(Ok(Error::Unauthorized.into())));
+}
+
+#[test]
+fn test_snapshots() {
+    let env = Env::default();
+    let (client, _, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+    let investor = Address::generate(&env);
+
+    let result = client.try_balance_of_at(&builder, &id, &1);
+    assert_eq!(result, Err(Ok(Error::InvalidSnapshot.into())));
+
+    client.transfer_property(&builder, &investor, &id, &100);
+    let first = client.snapshot(&builder, &id);
+    client.transfer_property(&builder, &investor, &id, &200);
+    client.transfer_property(&builder, &investor, &id, &50);
+    let second = client.snapshot(&builder, &id);
+    let third = client.snapshot(&builder, &id);
+    client.transfer_property(&investor, &builder, &id, &350);
+
+    assert_eq!((first, second, third), (1, 2, 3));
+    assert_eq!(client.balance_of_at(&investor, &id, &first), 100);
+    assert_eq!(client.balance_of_at(&builder, &id, &first), 900);
+    assert_eq!(client.balance_of_at(&investor, &id, &second), 350);
+    assert_eq!(client.balance_of_at(&investor, &id, &third), 350);
+    assert_eq!(client.balance_of(&investor, &id), 0);
+    assert_eq!(client.total_supply_at(&id, &second), 1_000);
+
+    let result = client.try_snapshot(&investor, &id);
+    assert_eq!(result, Err(Ok(Error::Unauthorized.into())));
+}
+
+#[test]
+fn test_governance() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.transfer_property(&builder, &alice, &id, &300);
+    client.transfer_property(&builder, &bob, &id, &200);
+    for status in [
+        PropertyStatus::Fundraising,
+        PropertyStatus::UnderConstruction,
+        PropertyStatus::Delivered,
+    ] {
+        client.set_property_status(&builder, &id, &status);
+    }
+
+    let hash = BytesN::from_array(&env, &[1; 32]);
+    let options = vec![&env, Symbol::new(&env, "sell"), Symbol::new(&env, "keep")];
+    let now = env.ledger().timestamp();
+    let result = client.try_propose(
+        &alice,
+        &id,
+        &hash,
+        &vec![&env, Symbol::new(&env, "sell")],
+        &ProposalAction::Record,
+        &now,
+        &(now + 100),
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidProposal.into())));
+
+    let sell = client.propose(
+        &alice,
+        &id,
+        &hash,
+        &options,
+        &ProposalAction::SetStatus(PropertyStatus::Sold),
+        &now,
+        &(now + 100),
+    );
+
+    // Fractions moved after the proposal do not count.
+    client.transfer_property(&builder, &alice, &id, &400);
+    assert_eq!(client.vote(&alice, &sell, &0), 300);
+    assert_eq!(client.vote(&bob, &sell, &1), 200);
+    assert_eq!(client.vote(&builder, &sell, &0), 500);
+    let result = client.try_vote(&alice, &sell, &1);
+    assert_eq!(result, Err(Ok(Error::AlreadyVoted.into())));
+    let result = client.try_vote(&Address::generate(&env), &sell, &0);
+    assert_eq!(result, Err(Ok(Error::NoVotingPower.into())));
+    let result = client.try_finalize_proposal(&sell);
+    assert_eq!(result, Err(Ok(Error::VotingNotEnded.into())));
+
+    // Nothing moves on a paused property, votes and outcomes included.
+    client.grant_role(&admin, &Role::Pauser, &admin);
+    client.pause_property(&admin, &id);
+    let result = client.try_vote(&Address::generate(&env), &sell, &0);
+    assert_eq!(result, Err(Ok(Error::PropertyPaused.into())));
+    env.ledger().with_mut(|li| li.timestamp = now + 100);
+    let result = client.try_finalize_proposal(&sell);
+    assert_eq!(result, Err(Ok(Error::PropertyPaused.into())));
+    client.unpause_property(&admin, &id);
+
+    let result = client.try_vote(&bob, &sell, &0);
+    assert_eq!(result, Err(Ok(Error::VotingClosed.into())));
+    assert_eq!(client.finalize_proposal(&sell), ProposalStatus::Executed);
+    assert_eq!(client.get_proposal(&sell).tally, vec![&env, 800, 200]);
+    assert_eq!(client.get_property(&id).status, PropertyStatus::Sold);
+
+    client.set_governance_params(
+        &admin,
+        &id,
+        &GovernanceParams {
+            quorum_bps: 5_000,
+            approval_bps: 5_000,
+        },
+    );
+    let now = env.ledger().timestamp();
+    let repairs = client.propose(
+        &bob,
+        &id,
+        &hash,
+        &options,
+        &ProposalAction::Record,
+        &now,
+        &(now + 100),
+    );
+    client.vote(&bob, &repairs, &0);
+    env.ledger().with_mut(|li| li.timestamp = now + 100);
+    assert_eq!(client.finalize_proposal(&repairs), ProposalStatus::Rejected);
+    assert_eq!(client.get_proposal(&repairs).winning_option, None);
+}
+
+#[test]
+fn test_redeem_after_sale() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 3);
+    let investor = Address::generate(&env);
+    let (brl, brl_admin) = create_token(&env, &admin);
+    brl_admin.mint(&builder, &1_000);
+    client.transfer_property(&builder, &investor, &id, &1);
+
+    let result = client.try_deposit_sale_proceeds(&builder, &id, &brl, &1_000);
+    assert_eq!(result, Err(Ok(Error::InvalidPropertyStatus.into())));
+    for status in [
+        PropertyStatus::Fundraising,
+        PropertyStatus::UnderConstruction,
+        PropertyStatus::Delivered,
+    ] {
+        client.set_property_status(&builder, &id, &status);
+    }
+    let result = client.try_redeem(&investor, &id, &1);
+    assert_eq!(result, Err(Ok(Error::InvalidPropertyStatus.into())));
+
+    client.deposit_sale_proceeds(&builder, &id, &brl, &1_000);
+    assert_eq!(client.get_property(&id).status, PropertyStatus::Sold);
+
+    // 1 of 3 fractions gets 333, the remaining 667 go to the other two.
+    assert_eq!(client.redeem(&investor, &id, &1), 333);
+    assert_eq!(client.total_supply(&id), 2);
+    let result = client.try_redeem(&investor, &id, &1);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance.into())));
+    assert_eq!(client.redeem(&builder, &id, &2), 667);
+
+    let brl_client = TokenClient::new(&env, &brl);
+    assert_eq!(brl_client.balance(&investor), 333);
+    assert_eq!(brl_client.balance(&builder), 667);
+    assert_eq!(client.total_supply(&id), 0);
+    assert_eq!(client.sale_proceeds(&id).unwrap().remaining, 0);
+}
+
+#[test]
+fn test_mint() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+    let minter = Address::generate(&env);
+    let investor = Address::generate(&env);
+
+    let result = client.try_mint(&minter, &id, &investor, &500);
+    assert_eq!(result, Err(Ok(rbac::Error::MissingRole.into())));
+    client.grant_role(&admin, &Role::Minter, &minter);
+
+    let result = client.try_set_max_supply(&admin, &id, &Some(999));
+    assert_eq!(result, Err(Ok(Error::SupplyCapExceeded.into())));
+    client.set_max_supply(&admin, &id, &Some(2_000));
+    assert_eq!(client.quote_price(&id), 1_000);
+
+    client.mint(&minter, &id, &investor, &1_000);
+    assert_eq!(client.total_supply(&id), 2_000);
+    assert_eq!(client.balance_of(&investor, &id), 1_000);
+    assert_eq!(client.quote_price(&id), 500);
+    let result = client.try_mint(&minter, &id, &investor, &1);
+    assert_eq!(result, Err(Ok(Error::SupplyCapExceeded.into())));
+
+    client.set_max_supply(&admin, &id, &None);
+    client.mint(&minter, &id, &builder, &1);
+    assert_eq!(client.total_supply(&id), 2_001);
+}
+
+#[test]
+fn test_holder_and_portfolio_indexes() {
+    let env = Env::default();
+    let (client, _, builder) = setup(&env);
+    let first = register(&env, &client, &builder, 1_000);
+    let second = register(&env, &client, &builder, 1_000);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.transfer_property(&builder, &alice, &first, &100);
+    client.transfer_property(&builder, &bob, &first, &100);
+    client.transfer_property(&builder, &alice, &second, &100);
+    assert_eq!(client.holder_count(&first), 3);
+    assert_eq!(
+        client.holders_of(&first, &0, &10),
+        vec![&env, builder.clone(), alice.clone(), bob.clone()]
+    );
+    assert_eq!(client.holders_of(&first, &1, &1), vec![&env, alice.clone()]);
+    assert_eq!(client.holders_of(&first, &5, &10).len(), 0);
+    // A zero limit asks for a full page, as it does for `list_properties`.
+    assert_eq!(client.holders_of(&first, &0, &0).len(), 3);
+    assert_eq!(
+        client.properties_of(&alice, &0, &10),
+        vec![&env, first, second]
+    );
+    assert_eq!(
+        client.properties_of(&alice, &0, &0),
+        vec![&env, first, second]
+    );
+
+    // Emptying a balance moves the last entry into the freed slot.
+    client.transfer_property(&alice, &bob, &first, &100);
+    assert_eq!(client.holder_count(&first), 2);
+    assert_eq!(
+        client.holders_of(&first, &0, &10),
+        vec![&env, builder.clone(), bob.clone()]
+    );
+    assert_eq!(client.properties_of(&alice, &0, &10), vec![&env, second]);
+    assert_eq!(
+        client.properties_of(&builder, &0, &10),
+        vec![&env, first, second]
+    );
+}
+
+#[test]
+fn test_list_properties() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let other_builder = add_builder(&env, &client, &admin);
+    let aurora = register(&env, &client, &builder, 1_000);
+    let boreal = client.register_property(
+        &other_builder,
+        &String::from_str(&env, "Residencial Boreal"),
+        &1_000_000,
+        &500_000,
+        &1_000,
+        &String::from_str(&env, "Construtora Meridian"),
+        &String::from_str(&env, "ipfs://boreal"),
+        &String::from_str(&env, "BOR"),
+    );
+    let cosmos = register(&env, &client, &builder, 1_000);
+    client.set_property_status(&builder, &cosmos, &PropertyStatus::Fundraising);
+    assert_eq!(client.property_count(), 3);
+
+    let ids = |page: PropertyPage| {
+        let mut ids = Vec::new(&env);
+        for property in page.properties.iter() {
+            ids.push_back(property.id);
+        }
+        (ids, page.next_cursor)
+    };
+
+    let page = client.list_properties(&0, &2, &PropertyFilter::any(&env));
+    assert_eq!(ids(page), (vec![&env, aurora, boreal], Some(cosmos)));
+    let page = client.list_properties(&cosmos, &2, &PropertyFilter::any(&env));
+    assert_eq!(ids(page), (vec![&env, cosmos], None));
+
+    let by_builder = PropertyFilter {
+        builder: Some(builder.clone()),
+        ..PropertyFilter::any(&env)
+    };
+    let page = client.list_properties(&0, &10, &by_builder);
+    assert_eq!(ids(page), (vec![&env, aurora, cosmos], None));
+
+    let fundraising = PropertyFilter {
+        statuses: vec![&env, PropertyStatus::Fundraising],
+        ..PropertyFilter::any(&env)
+    };
+    let page = client.list_properties(&0, &10, &fundraising);
+    assert_eq!(ids(page), (vec![&env, cosmos], None));
+
+    let half_funded = PropertyFilter {
+        min_funding_bps: Some(4_000),
+        max_funding_bps: Some(6_000),
+        sigla: Some(String::from_str(&env, "BOR")),
+        ..PropertyFilter::any(&env)
+    };
+    let page = client.list_properties(&0, &10, &half_funded);
+    assert_eq!(ids(page), (vec![&env, boreal], None));
+
+    // A zero limit still makes progress instead of paging forever.
+    let page = client.list_properties(&0, &0, &PropertyFilter::any(&env));
+    assert_eq!(ids(page), (vec![&env, aurora, boreal, cosmos], None));
+
+    // Legacy properties may have no funding target.
+    env.as_contract(&client.address, || {
+        let mut property = storage::read_property(&env, aurora).unwrap();
+        property.ele_quer = 0;
+        storage::write_property(&env, &property);
+    });
+    let unfunded = PropertyFilter {
+        max_funding_bps: Some(0),
+        ..PropertyFilter::any(&env)
+    };
+    let page = client.list_properties(&0, &10, &unfunded);
+    assert_eq!(ids(page), (vec![&env, aurora, cosmos], None));
+}
+
+#[test]
+fn test_typed_events() {
+    let env = Env::default();
+    let (client, _, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+    let investor = Address::generate(&env);
+
+    client.transfer_property(&builder, &investor, &id, &100);
+    let (contract, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(contract, client.address);
+    assert_eq!(
+        topics,
+        (
+            Symbol::new(&env, "property_transferred"),
+            events::EVENT_VERSION,
+            id
+        )
+            .into_val(&env)
+    );
+    let event: events::PropertyTransferred = data.into_val(&env);
+    assert_eq!(
+        event,
+        events::PropertyTransferred {
+            property_id: id,
+            from: builder.clone(),
+            to: investor.clone(),
+            amount: 100,
+        }
+    );
+
+    client.set_approval_for_all(&investor, &builder, &true);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (
+            Symbol::new(&env, "approval_for_all"),
+            events::EVENT_VERSION,
+            investor.clone()
+        )
+            .into_val(&env)
+    );
+    let event: events::ApprovalForAll = data.into_val(&env);
+    assert!(event.approved);
+}
+
+mod vault {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Bytes, Env};
+
+    /// Receiver that keeps the last delivery and refuses more than 500 fractions.
+    #[contract]
+    pub struct Vault;
+
+    #[contractimpl]
+    impl Vault {
+        pub fn on_fraction_received(
+            env: Env,
+            _operator: Address,
+            from: Address,
+            id: u128,
+            amount: i128,
+            data: Bytes,
+        ) -> bool {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("last"), &(from, id, amount, data));
+            amount <= 500
+        }
+
+        pub fn last(env: Env) -> Option<(Address, u128, i128, Bytes)> {
+            env.storage().instance().get(&symbol_short!("last"))
+        }
+    }
+}
+
+#[test]
+fn test_receiver_hooks() {
+    let env = Env::default();
+    let (client, _, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+    let vault_id = env.register(vault::Vault, ());
+    let vault = vault::VaultClient::new(&env, &vault_id);
+
+    // Not opted in: plain transfers do not call the hook.
+    client.transfer_property(&builder, &vault_id, &id, &10);
+    assert_eq!(vault.last(), None);
+
+    client.set_fraction_receiver(&vault_id, &true);
+    client.transfer_property(&builder, &vault_id, &id, &20);
+    assert_eq!(
+        vault.last(),
+        Some((builder.clone(), id, 20, Bytes::new(&env)))
+    );
+
+    let data = Bytes::from_array(&env, &[1, 2, 3]);
+    client.transfer_and_call(&builder, &vault_id, &id, &30, &data);
+    assert_eq!(vault.last(), Some((builder.clone(), id, 30, data.clone())));
+    assert_eq!(client.balance_of(&vault_id, &id), 60);
+
+    let result = client.try_transfer_and_call(&builder, &vault_id, &id, &501, &data);
+    assert_eq!(result, Err(Ok(Error::ReceiverRejected.into())));
+    assert_eq!(client.balance_of(&vault_id, &id), 60);
+
+    // An account cannot implement the hook, so `transfer_and_call` to it fails.
+    let investor = Address::generate(&env);
+    let result = client.try_transfer_and_call(&builder, &investor, &id, &1, &data);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_vesting() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+    let investor = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let schedule = VestingSchedule {
+        amount: 1_000,
+        start: 1_000,
+        cliff: 1_100,
+        end: 2_000,
+        lockup_end: 1_200,
+    };
+    let invalid = VestingSchedule {
+        cliff: 900,
+        ..schedule.clone()
+    };
+    let result = client.try_create_vesting(&admin, &builder, &id, &invalid);
+    assert_eq!(result, Err(Ok(Error::InvalidVesting.into())));
+    client.create_vesting(&admin, &builder, &id, &schedule);
+
+    assert_eq!(client.locked_balance(&builder, &id), 1_000);
+    assert_eq!(client.vested_balance(&builder, &id), 0);
+    let result = client.try_transfer_property(&builder, &investor, &id, &1);
+    assert_eq!(result, Err(Ok(Error::FractionsLocked.into())));
+    assert_eq!(client.can_transfer(&builder, &investor, &id, &1), 1038);
+
+    // Past the cliff but still in lock-up: vested fractions cannot move either.
+    env.ledger().with_mut(|li| li.timestamp = 1_150);
+    assert_eq!(client.locked_balance(&builder, &id), 1_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_300);
+    assert_eq!(client.vested_balance(&builder, &id), 300);
+    client.transfer_property(&builder, &investor, &id, &300);
+    let result = client.try_transfer_property(&builder, &investor, &id, &1);
+    assert_eq!(result, Err(Ok(Error::FractionsLocked.into())));
+    client.transfer_property(&investor, &builder, &id, &300);
+    assert_eq!(client.vested_balance(&investor, &id), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert_eq!(client.locked_balance(&builder, &id), 0);
+    client.transfer_property(&builder, &investor, &id, &1_000);
+}
+
+#[test]
+fn test_vesting_on_sale_and_redeem() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 3);
+    let investor = Address::generate(&env);
+    let (brl, brl_admin) = create_token(&env, &admin);
+    brl_admin.mint(&investor, &666_668);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let schedule = VestingSchedule {
+        amount: 2,
+        start: 1_000,
+        cliff: 1_100,
+        end: 2_000,
+        lockup_end: 1_200,
+    };
+    client.create_vesting(&admin, &builder, &id, &schedule);
+
+    // Primary sales may only draw on inventory the schedule leaves unlocked.
+    client.set_payment_token(&admin, &brl, &true);
+    client.set_property_status(&builder, &id, &PropertyStatus::Fundraising);
+    client.buy_fractions(&investor, &id, &brl, &1, &333_334);
+    assert_eq!(client.balance_of(&investor, &id), 1);
+    let result = client.try_buy_fractions(&investor, &id, &brl, &1, &333_334);
+    assert_eq!(result, Err(Ok(Error::FractionsLocked.into())));
+
+    for status in [PropertyStatus::UnderConstruction, PropertyStatus::Delivered] {
+        client.set_property_status(&builder, &id, &status);
+    }
+    client.deposit_sale_proceeds(&builder, &id, &brl, &1_000);
+    let result = client.try_redeem(&builder, &id, &1);
+    assert_eq!(result, Err(Ok(Error::FractionsLocked.into())));
+    assert_eq!(client.redeem(&investor, &id, &1), 333);
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert_eq!(client.redeem(&builder, &id, &2), 667);
+}
+
+#[test]
+fn test_holding_limits() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+    let officer = Address::generate(&env);
+    let investor = Address::generate(&env);
+    client.grant_role(&admin, &Role::Compliance, &officer);
+
+    let invalid = HoldingLimits {
+        max_balance: 0,
+        max_share_bps: 10_001,
+    };
+    let result = client.try_set_holding_limits(&officer, &id, &invalid);
+    assert_eq!(result, Err(Ok(Error::InvalidLimits.into())));
+    client.set_holding_limits(
+        &officer,
+        &id,
+        &HoldingLimits {
+            max_balance: 300,
+            max_share_bps: 2_000,
+        },
+    );
+    assert_eq!(client.holding_headroom(&investor, &id), Some(200));
+    assert_eq!(client.holding_headroom(&builder, &id), None);
+
+    let result = client.try_transfer_property(&builder, &investor, &id, &250);
+    assert_eq!(result, Err(Ok(Error::ConcentrationLimitExceeded.into())));
+    assert_eq!(client.can_transfer(&builder, &investor, &id, &250), 1040);
+    client.transfer_property(&builder, &investor, &id, &200);
+    assert_eq!(client.holding_headroom(&investor, &id), Some(0));
+    // The builder is exempt, so fractions can always flow back to it.
+    client.transfer_property(&investor, &builder, &id, &200);
+
+    client.set_holding_limits(
+        &officer,
+        &id,
+        &HoldingLimits {
+            max_balance: 100,
+            max_share_bps: 0,
+        },
+    );
+    let result = client.try_transfer_property(&builder, &investor, &id, &101);
+    assert_eq!(result, Err(Ok(Error::HoldingLimitExceeded.into())));
+    client.set_holding_limits(&officer, &id, &HoldingLimits::default());
+
+    // One fraction costs 1_000, so the ceiling is 150 fractions a year.
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    assert_eq!(client.annual_headroom(&investor), None);
+    client.set_annual_cap(&officer, &InvestorCategory::Retail, &150_000);
+    client.transfer_property(&builder, &investor, &id, &100);
+    assert_eq!(client.annual_headroom(&investor), Some(50_000));
+    let result = client.try_transfer_property(&builder, &investor, &id, &60);
+    assert_eq!(result, Err(Ok(Error::AnnualCapExceeded.into())));
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1_000 + 365 * 86_400);
+    assert_eq!(client.annual_headroom(&investor), Some(150_000));
+    client.transfer_property(&builder, &investor, &id, &60);
+    assert_eq!(client.balance_of(&investor, &id), 160);
+}
+
+#[test]
+fn test_forced_transfer_and_recovery() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+    let other = register(&env, &client, &builder, 500);
+    let officer = Address::generate(&env);
+    let lost = Address::generate(&env);
+    let new = Address::generate(&env);
+    let reason = BytesN::from_array(&env, &[7; 32]);
+    client.grant_role(&admin, &Role::Compliance, &officer);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    client.transfer_property(&builder, &lost, &id, &300);
+    client.transfer_property(&builder, &lost, &other, &50);
+    client.create_vesting(
+        &admin,
+        &lost,
+        &id,
+        &VestingSchedule {
+            amount: 300,
+            start: 1_000,
+            cliff: 1_000,
+            end: 2_000,
+            lockup_end: 2_000,
+        },
+    );
+
+    let result = client.try_forced_transfer(&builder, &lost, &new, &id, &100, &reason);
+    assert_eq!(result, Err(Ok(rbac::Error::MissingRole.into())));
+
+    // Without a delay the transfer happens at once, lock-up notwithstanding.
+    let queued = client.forced_transfer(&officer, &lost, &builder, &id, &100, &reason);
+    assert_eq!(queued, None);
+    assert_eq!(client.balance_of(&lost, &id), 200);
+
+    // Recovering onto an account with its own schedule would unlock one of them.
+    let scheduled = Address::generate(&env);
+    client.transfer_property(&builder, &scheduled, &id, &10);
+    client.create_vesting(
+        &admin,
+        &scheduled,
+        &id,
+        &VestingSchedule {
+            amount: 10,
+            start: 1_000,
+            cliff: 1_000,
+            end: 2_000,
+            lockup_end: 2_000,
+        },
+    );
+    let result = client.try_recover_account(&officer, &lost, &scheduled, &reason);
+    assert_eq!(result, Err(Ok(Error::InvalidRecovery.into())));
+    assert_eq!(client.balance_of(&lost, &id), 200);
+
+    client.set_recovery_delay(&admin, &86_400);
+    let request = client
+        .recover_account(&officer, &lost, &new, &reason)
+        .unwrap();
+    assert_eq!(client.balance_of(&lost, &id), 200);
+    let result = client.try_execute_recovery(&officer, &request);
+    assert_eq!(result, Err(Ok(Error::RecoveryNotReady.into())));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + 86_400);
+    client.execute_recovery(&officer, &request);
+    assert_eq!(client.get_recovery(&request), None);
+    assert_eq!(client.balance_of(&lost, &id), 0);
+    assert_eq!(client.balance_of(&lost, &other), 0);
+    assert_eq!(client.balance_of(&new, &id), 200);
+    assert_eq!(client.balance_of(&new, &other), 50);
+    assert_eq!(client.properties_of(&lost, &0, &10).len(), 0);
+    assert!(client.get_vesting(&new, &id).is_some());
+
+    let request = client
+        .forced_transfer(&officer, &new, &lost, &other, &50, &reason)
+        .unwrap();
+    client.cancel_recovery(&officer, &request);
+    let result = client.try_execute_recovery(&officer, &request);
+    assert_eq!(result, Err(Ok(Error::RecoveryNotFound.into())));
+}
+
+#[test]
+fn test_freezes() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 1_000);
+    let officer = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.grant_role(&admin, &Role::Compliance, &officer);
+    client.transfer_property(&builder, &investor, &id, &300);
+
+    client.freeze_address(&officer, &investor);
+    assert!(client.is_frozen(&investor));
+    assert_eq!(client.frozen_balance(&investor, &id), 300);
+    let result = client.try_transfer_property(&investor, &other, &id, &1);
+    assert_eq!(result, Err(Ok(Error::AccountFrozen.into())));
+    let result = client.try_transfer_property(&builder, &investor, &id, &1);
+    assert_eq!(result, Err(Ok(Error::AccountFrozen.into())));
+    // Other holders are unaffected.
+    client.transfer_property(&builder, &other, &id, &10);
+    client.unfreeze_address(&officer, &investor);
+
+    client.freeze_partial(&officer, &investor, &id, &200);
+    assert_eq!(client.frozen_balance(&investor, &id), 200);
+    let result = client.try_transfer_property(&investor, &other, &id, &101);
+    assert_eq!(result, Err(Ok(Error::FractionsFrozen.into())));
+    assert_eq!(client.can_transfer(&investor, &other, &id, &101), 1047);
+    client.transfer_property(&investor, &other, &id, &100);
+
+    client.freeze_partial(&officer, &investor, &id, &0);
+    assert_eq!(client.frozen_balance(&investor, &id), 0);
+    client.transfer_property(&investor, &other, &id, &200);
+}
+
+#[test]
+fn test_freeze_blocks_redeem() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let id = register(&env, &client, &builder, 3);
+    let officer = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let (brl, brl_admin) = create_token(&env, &admin);
+    brl_admin.mint(&builder, &1_000);
+    client.grant_role(&admin, &Role::Compliance, &officer);
+    client.transfer_property(&builder, &investor, &id, &1);
+    for status in [
+        PropertyStatus::Fundraising,
+        PropertyStatus::UnderConstruction,
+        PropertyStatus::Delivered,
+    ] {
+        client.set_property_status(&builder, &id, &status);
+    }
+    client.deposit_sale_proceeds(&builder, &id, &brl, &1_000);
+
+    client.freeze_address(&officer, &investor);
+    let result = client.try_redeem(&investor, &id, &1);
+    assert_eq!(result, Err(Ok(Error::AccountFrozen.into())));
+    client.unfreeze_address(&officer, &investor);
+
+    client.freeze_partial(&officer, &builder, &id, &1);
+    let result = client.try_redeem(&builder, &id, &2);
+    assert_eq!(result, Err(Ok(Error::FractionsFrozen.into())));
+    assert_eq!(client.redeem(&builder, &id, &1), 333);
+    assert_eq!(client.redeem(&investor, &id, &1), 333);
+}
+
+#[test]
+fn test_upgrade_and_migrate() {
+    let env = Env::default();
+    let (client, admin, builder) = setup(&env);
+    let first = register(&env, &client, &builder, 1_000);
+    let second = register(&env, &client, &builder, 500);
+    let investor = Address::generate(&env);
+    client.transfer_property(&builder, &investor, &first, &250);
+
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+    let result = client.try_migrate(&admin, &0, &0);
+    assert_eq!(result, Err(Ok(Error::AlreadyMigrated.into())));
+
+    let properties = [client.get_property(&first), client.get_property(&second)];
+
+    let hash = upload_empty_wasm(&env);
+    let result = client.try_upgrade(&investor, &hash);
+    assert_eq!(result, Err(Ok(rbac::Error::MissingRole.into())));
+    client.upgrade(&admin, &hash);
+    // The empty module exports nothing, so the old entrypoints are gone.
+    assert!(client.try_balance_of(&investor, &first).is_err());
+
+    // Stand-in for the new release: this crate's code at the same address,
+    // over the storage the upgrade left behind.
+    env.register_at(&client.address, RealEstateTokenContract, ());
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+    let result = client.try_migrate(&investor, &0, &0);
+    assert_eq!(result, Err(Ok(rbac::Error::MissingRole.into())));
+    let result = client.try_migrate(&admin, &0, &0);
+    assert_eq!(result, Err(Ok(Error::AlreadyMigrated.into())));
+
+    assert_eq!(client.get_property(&first), properties[0]);
+    assert_eq!(client.get_property(&second), properties[1]);
+    assert_eq!(client.balance_of(&investor, &first), 250);
+    assert_eq!(client.balance_of(&builder, &first), 750);
+    assert_eq!(client.balance_of(&builder, &second), 500);
+    assert_eq!(client.holder_count(&first), 2);
+    client.transfer_property(&investor, &builder, &first, &50);
+}
+
+#[test]
+fn test_timelocked_actions() {
+    let env = Env::default();
+    let (client, admin, _) = setup(&env);
+    let treasury = Address::generate(&env);
+    let (usdc, _) = create_token(&env, &admin);
+
+    let result = client.try_initialize(&treasury);
+    assert_eq!(result, Err(Ok(rbac::Error::AlreadyInitialized.into())));
+
+    client.set_min_delay(&admin, &86_400);
+    let result = client.try_set_payment_token(&admin, &usdc, &true);
+    assert_eq!(result, Err(Ok(rbac::Error::TimelockRequired.into())));
+    let result = client.try_set_treasury(&admin, &treasury);
+    assert_eq!(result, Err(Ok(rbac::Error::TimelockRequired.into())));
+    let result = client.try_set_recovery_delay(&admin, &0);
+    assert_eq!(result, Err(Ok(rbac::Error::TimelockRequired.into())));
+    let hash = upload_empty_wasm(&env);
+    let result = client.try_upgrade(&admin, &hash);
+    assert_eq!(result, Err(Ok(rbac::Error::TimelockRequired.into())));
+
+    let recovery_delay = TimelockAction::SetRecoveryDelay(3_600);
+    let operation_id = client.schedule_action(&admin, &recovery_delay, &86_400);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (
+            Symbol::new(&env, "action_scheduled"),
+            events::EVENT_VERSION,
+            operation_id.clone()
+        )
+            .into_val(&env)
+    );
+    let event: events::ActionScheduled = data.into_val(&env);
+    assert_eq!(event.action, recovery_delay);
+    client.schedule_action(
+        &admin,
+        &TimelockAction::SetPaymentToken(usdc.clone(), true),
+        &86_400,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 86_400);
+    client.execute_action(&admin, &recovery_delay);
+    assert_eq!(client.recovery_delay(), 3_600);
+    client.execute_action(&admin, &TimelockAction::SetPaymentToken(usdc.clone(), true));
+    assert_eq!(client.action_eta(&operation_id), None);
+
+    let action = TimelockAction::SetTreasury(treasury);
+    let operation_id = client.schedule_action(&admin, &action, &86_400);
+    client.cancel_action(&admin, &operation_id);
+    let result = client.try_execute_action(&admin, &action);
+    assert_eq!(result, Err(Ok(rbac::Error::OperationNotFound.into())));
 }
@@ -0,0 +1,14 @@
+//! Helpers shared by the tests of this contract and of contracts that use it.
+use soroban_sdk::{Bytes, BytesN, Env};
+
+/// Uploads the smallest wasm the host accepts, an empty module that only
+/// carries the `contractenvmetav0` section declaring interface version 22.
+/// Upgrading to it proves the code was swapped: every entrypoint disappears.
+pub fn upload_empty_wasm(env: &Env) -> BytesN<32> {
+    let wasm: [u8; 40] = [
+        0, b'a', b's', b'm', 1, 0, 0, 0, 0, 30, 17, b'c', b'o', b'n', b't', b'r', b'a', b'c', b't',
+        b'e', b'n', b'v', b'm', b'e', b't', b'a', b'v', b'0', 0, 0, 0, 0, 0, 0, 0, 22, 0, 0, 0, 0,
+    ];
+    env.deployer()
+        .upload_contract_wasm(Bytes::from_slice(env, &wasm))
+}
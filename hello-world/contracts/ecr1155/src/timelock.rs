@@ -0,0 +1,89 @@
+//! Privileged operations behind the `rbac` timelock.
+//!
+//! While an admin has set a minimum delay, upgrades, role grants, the recovery
+//! delay and changes to where and how sale money is paid cannot be made
+//! directly. They are scheduled as a [`TimelockAction`], published in full so
+//! holders can see what is coming, and executed once the delay has passed
+//! unless an admin cancels them first. See [`rbac::timelock`].
+use rbac::timelock::{Action, RbacAction};
+use soroban_sdk::{contractimpl, contracttype, Address, BytesN, Env};
+
+use crate::{
+    events, recovery, sale, upgrade, RealEstateTokenContract, RealEstateTokenContractArgs,
+    RealEstateTokenContractClient,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TimelockAction {
+    /// Role and timelock changes.
+    Rbac(RbacAction),
+    /// `upgrade` to the given wasm hash.
+    Upgrade(BytesN<32>),
+    /// `set_payment_token(token, accepted)`.
+    SetPaymentToken(Address, bool),
+    /// `set_treasury(treasury)`.
+    SetTreasury(Address),
+    /// `set_recovery_delay(delay)`.
+    SetRecoveryDelay(u64),
+}
+
+impl Action for TimelockAction {
+    fn apply(self, env: &Env, admin: &Address) {
+        match self {
+            TimelockAction::Rbac(action) => action.apply(env, admin),
+            TimelockAction::Upgrade(new_wasm_hash) => upgrade::install(env, new_wasm_hash),
+            TimelockAction::SetPaymentToken(token, accepted) => {
+                sale::apply_payment_token(env, token, accepted)
+            }
+            TimelockAction::SetTreasury(treasury) => sale::apply_treasury(env, treasury),
+            TimelockAction::SetRecoveryDelay(delay) => recovery::apply_recovery_delay(env, delay),
+        }
+    }
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// See [`rbac::timelock::set_min_delay`].
+    pub fn set_min_delay(env: Env, admin: Address, delay: u64) {
+        rbac::timelock::set_min_delay(&env, &admin, delay);
+    }
+
+    pub fn min_delay(env: Env) -> u64 {
+        rbac::timelock::min_delay(&env)
+    }
+
+    /// See [`rbac::timelock::schedule`]. Returns the operation id.
+    pub fn schedule_action(
+        env: Env,
+        admin: Address,
+        action: TimelockAction,
+        delay: u64,
+    ) -> BytesN<32> {
+        let (operation_id, eta) = rbac::timelock::schedule_action(&env, &admin, &action, delay);
+
+        events::ActionScheduled {
+            operation_id: operation_id.clone(),
+            action,
+            eta,
+        }
+        .publish(&env);
+
+        operation_id
+    }
+
+    /// See [`rbac::timelock::cancel`].
+    pub fn cancel_action(env: Env, admin: Address, operation_id: BytesN<32>) {
+        rbac::timelock::cancel(&env, &admin, &operation_id);
+    }
+
+    /// See [`rbac::timelock::execute`].
+    pub fn execute_action(env: Env, admin: Address, action: TimelockAction) {
+        rbac::timelock::execute_action(&env, &admin, action);
+    }
+
+    /// See [`rbac::timelock::eta`].
+    pub fn action_eta(env: Env, operation_id: BytesN<32>) -> Option<u64> {
+        rbac::timelock::eta(&env, &operation_id)
+    }
+}
@@ -0,0 +1,81 @@
+//! In-place code upgrades and the storage migrations that go with them.
+//!
+//! The layout of stored entries is versioned by [`SCHEMA_VERSION`]. After
+//! `upgrade` installs code that expects a newer layout, the admin calls
+//! `migrate` to rewrite older entries, one version step at a time. Deployments
+//! from before the version was stored count as version 1.
+use soroban_sdk::{contractimpl, panic_with_error, Address, BytesN, Env};
+
+use crate::{
+    events, storage, Error, RealEstateTokenContract, RealEstateTokenContractArgs,
+    RealEstateTokenContractClient, Role, MIGRATION_BATCH_SIZE,
+};
+
+/// Layout written by this code:
+/// 1. per-key entries under [`storage::DataKey`].
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Swaps the contract's code for the uploaded wasm `new_wasm_hash`.
+pub(crate) fn install(env: &Env, new_wasm_hash: BytesN<32>) {
+    env.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+    events::ContractUpgraded {
+        wasm_hash: new_wasm_hash,
+    }
+    .publish(env);
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Admin-only. Timelocked. Replaces the contract's code with the uploaded
+    /// wasm `new_wasm_hash`, keeping its address and storage. Call `migrate`
+    /// next if the new code bumps [`SCHEMA_VERSION`].
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) {
+        rbac::require_role(&env, &admin, Role::Admin);
+        rbac::timelock::require_unlocked(&env);
+        install(&env, new_wasm_hash);
+    }
+
+    /// Admin-only. Brings stored entries up to [`SCHEMA_VERSION`]. Panics with
+    /// `AlreadyMigrated` if they already are.
+    ///
+    /// Properties are rewritten `limit` at a time starting at id `cursor`, so a
+    /// large catalog fits in several transactions. Returns the cursor of the
+    /// next batch, or `None` once the last batch has run and the stored version
+    /// has been bumped.
+    pub fn migrate(env: Env, admin: Address, cursor: u128, limit: u32) -> Option<u128> {
+        rbac::require_role(&env, &admin, Role::Admin);
+
+        let from = storage::read_schema_version(&env);
+        if from >= SCHEMA_VERSION {
+            panic_with_error!(&env, Error::AlreadyMigrated);
+        }
+
+        let limit = if limit == 0 {
+            MIGRATION_BATCH_SIZE
+        } else {
+            limit.min(MIGRATION_BATCH_SIZE)
+        };
+        let next_id = storage::read_next_property_id(&env);
+        let start = cursor.max(1);
+        let end = start.saturating_add(limit as u128).min(next_id);
+        // Steps from each older layout go here, oldest first, each rewriting
+        // properties `start..end`.
+        storage::extend_instance(&env);
+        if end < next_id {
+            return Some(end);
+        }
+
+        storage::write_schema_version(&env, SCHEMA_VERSION);
+        events::SchemaMigrated {
+            from,
+            to: SCHEMA_VERSION,
+        }
+        .publish(&env);
+        None
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        storage::read_schema_version(&env)
+    }
+}
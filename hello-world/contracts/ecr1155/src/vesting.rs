@@ -0,0 +1,131 @@
+//! Vesting and lock-up schedules on a holder's fractions of one property.
+//!
+//! A schedule covers `amount` fractions of the holder's balance. Nothing vests
+//! before the cliff, then the amount vests linearly from `start` to `end`, and
+//! until `lockup_end` none of it can move, vested or not. Transfers may only
+//! take fractions outside the still-locked part; fractions bought or received
+//! on top of the schedule are never locked.
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env};
+
+use crate::{
+    events,
+    math::{self, Rounding},
+    storage, Error, RealEstateTokenContract, RealEstateTokenContractArgs,
+    RealEstateTokenContractClient, Role,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    /// Fractions the schedule applies to.
+    pub amount: i128,
+    /// Ledger timestamp linear vesting is measured from.
+    pub start: u64,
+    /// Nothing vests before this timestamp.
+    pub cliff: u64,
+    /// Everything has vested from this timestamp on.
+    pub end: u64,
+    /// Nothing covered by the schedule moves before this timestamp.
+    pub lockup_end: u64,
+}
+
+impl VestingSchedule {
+    /// Fractions vested at `now`, rounded down.
+    pub fn vested(&self, env: &Env, now: u64) -> i128 {
+        if now < self.cliff {
+            0
+        } else if now >= self.end {
+            self.amount
+        } else {
+            math::mul_div(
+                env,
+                self.amount,
+                (now - self.start) as i128,
+                (self.end - self.start) as i128,
+                Rounding::Down,
+            )
+        }
+    }
+
+    /// Fractions still locked at `now`.
+    pub fn locked(&self, env: &Env, now: u64) -> i128 {
+        if now < self.lockup_end {
+            self.amount
+        } else {
+            self.amount - self.vested(env, now)
+        }
+    }
+}
+
+fn locked_balance(env: &Env, holder: &Address, id: u128, balance: i128) -> i128 {
+    match storage::read_vesting(env, holder, id) {
+        Some(schedule) => schedule.locked(env, env.ledger().timestamp()).min(balance),
+        None => 0,
+    }
+}
+
+/// Rejects moving `amount` out of a `balance` whose locked part would not stay
+/// covered.
+pub(crate) fn check_unlocked(
+    env: &Env,
+    holder: &Address,
+    id: u128,
+    amount: i128,
+    balance: i128,
+) -> Result<(), Error> {
+    if balance - amount < locked_balance(env, holder, id, balance) {
+        return Err(Error::FractionsLocked);
+    }
+    Ok(())
+}
+
+#[contractimpl]
+impl RealEstateTokenContract {
+    /// Admin-only. Puts `schedule` on `holder`'s fractions of `property_id`,
+    /// replacing any previous one.
+    pub fn create_vesting(
+        env: Env,
+        admin: Address,
+        holder: Address,
+        property_id: u128,
+        schedule: VestingSchedule,
+    ) {
+        rbac::require_role(&env, &admin, Role::Admin);
+
+        if storage::read_property(&env, property_id).is_none() {
+            panic_with_error!(&env, Error::InvalidProperty);
+        }
+        if schedule.amount <= 0
+            || schedule.cliff < schedule.start
+            || schedule.end < schedule.cliff
+            || schedule.end <= schedule.start
+        {
+            panic_with_error!(&env, Error::InvalidVesting);
+        }
+        storage::write_vesting(&env, &holder, property_id, &schedule);
+
+        events::VestingCreated {
+            property_id,
+            holder,
+            schedule,
+        }
+        .publish(&env);
+    }
+
+    pub fn get_vesting(env: Env, holder: Address, property_id: u128) -> Option<VestingSchedule> {
+        storage::read_vesting(&env, &holder, property_id)
+    }
+
+    /// Part of `holder`'s balance of `property_id` that is vested and out of
+    /// lock-up, i.e. free to transfer now.
+    pub fn vested_balance(env: Env, holder: Address, property_id: u128) -> i128 {
+        let balance = storage::read_balance(&env, &holder, property_id);
+        balance - locked_balance(&env, &holder, property_id, balance)
+    }
+
+    /// Part of `holder`'s balance of `property_id` that cannot move yet.
+    pub fn locked_balance(env: Env, holder: Address, property_id: u128) -> i128 {
+        let balance = storage::read_balance(&env, &holder, property_id);
+        locked_balance(&env, &holder, property_id, balance)
+    }
+}
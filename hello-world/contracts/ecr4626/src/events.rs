@@ -0,0 +1,143 @@
+//! Typed events for every state change of the loan contract.
+//!
+//! Each event is published with the topics `(name, EVENT_VERSION, key)`, where
+//! the key is the borrow or loan id, and its struct as the data. Any change to
+//! a struct's fields bumps [`EVENT_VERSION`].
+use soroban_sdk::{contracttype, Address, BytesN, Env, IntoVal, Symbol, Val};
+
+use crate::TimelockAction;
+
+/// Schema version, published as the second topic of every event.
+pub const EVENT_VERSION: u32 = 1;
+
+fn publish<K, D>(e: &Env, name: &str, key: K, data: D)
+where
+    K: IntoVal<Env, Val>,
+    D: IntoVal<Env, Val>,
+{
+    e.events()
+        .publish((Symbol::new(e, name), EVENT_VERSION, key), data);
+}
+
+/// The contract was pointed at an ecr1155 deployment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RwaTokenSet {
+    pub rwa_token: Address,
+}
+
+impl RwaTokenSet {
+    pub(crate) fn publish(self, e: &Env) {
+        e.events()
+            .publish((Symbol::new(e, "rwa_token_set"), EVENT_VERSION), self);
+    }
+}
+
+/// A builder opened a borrow request backed by one of its properties.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BorrowCreated {
+    pub borrow_id: u128,
+    pub builder: Address,
+    pub property_id: u128,
+    /// Ledger timestamp after which the borrow is no longer listed.
+    pub deadline: u64,
+    /// Offered yield, in basis points per year.
+    pub apy: u32,
+    /// Price of one fraction when the borrow was opened.
+    pub price: i128,
+    /// Funding progress of the property, in basis points.
+    pub funding_bps: u128,
+}
+
+impl BorrowCreated {
+    pub(crate) fn publish(self, e: &Env) {
+        publish(e, "borrow_created", self.borrow_id, self);
+    }
+}
+
+/// An investor funded a borrow.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanCreated {
+    pub loan_id: u128,
+    pub borrow_id: u128,
+    pub builder: Address,
+    pub investor: Address,
+    /// Amount lent, in payment token base units.
+    pub investment: u128,
+}
+
+impl LoanCreated {
+    pub(crate) fn publish(self, e: &Env) {
+        publish(e, "loan_created", self.loan_id, self);
+    }
+}
+
+/// A builder repaid a loan and got its collateral back. Schema only for now:
+/// `repay_loan` is still disabled and will publish it as `"loan_repaid"`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanRepaid {
+    pub loan_id: u128,
+    pub builder: Address,
+    /// Amount repaid, in payment token base units.
+    pub amount: i128,
+}
+
+/// A loan matured unpaid and its collateral went to the investors. Schema only
+/// for now: `execute_collateral` is still disabled and will publish it as
+/// `"loan_defaulted"`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanDefaulted {
+    pub loan_id: u128,
+    pub builder: Address,
+    /// Fractions handed over to investors.
+    pub collateral_amount: i128,
+}
+
+/// The contract's code was replaced.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractUpgraded {
+    pub wasm_hash: BytesN<32>,
+}
+
+impl ContractUpgraded {
+    pub(crate) fn publish(self, e: &Env) {
+        e.events()
+            .publish((Symbol::new(e, "contract_upgraded"), EVENT_VERSION), self);
+    }
+}
+
+/// Stored entries were migrated between schema versions.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SchemaMigrated {
+    pub from: u32,
+    pub to: u32,
+}
+
+impl SchemaMigrated {
+    pub(crate) fn publish(self, e: &Env) {
+        e.events()
+            .publish((Symbol::new(e, "schema_migrated"), EVENT_VERSION), self);
+    }
+}
+
+/// A privileged action was queued behind the timelock. The `rbac`
+/// `operation_scheduled` event carries only its id.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActionScheduled {
+    pub operation_id: BytesN<32>,
+    pub action: TimelockAction,
+    pub eta: u64,
+}
+
+impl ActionScheduled {
+    pub(crate) fn publish(self, e: &Env) {
+        publish(e, "action_scheduled", self.operation_id.clone(), self);
+    }
+}
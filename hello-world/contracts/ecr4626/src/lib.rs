@@ -1,11 +1,14 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, Address, Env, String};
-use chrono::{Datelike, Duration, Local, NaiveDate};
-use crate::ecr1155::Property;
+use ecr1155_interface::RealEstateTokenClient;
+use rbac::timelock::Action;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, panic_with_error, Address, BytesN, Env, Map, String,
+};
 
-mod ecr1155 {
-    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/ecr1155.wasm");
-}
+pub mod events;
+mod test;
+
+pub use rbac::{timelock::RbacAction, Role};
 
 #[contract]
 pub struct LoanContract;
@@ -23,6 +26,8 @@ pub enum Error {
     TokensAlreadyLocked,
     LoanNotFullyFunded,
     LoanNotMatured,
+    AlreadyMigrated,
+    MigrationPending,
 }
 
 impl From<Error> for soroban_sdk::Error {
@@ -39,163 +44,409 @@ impl From<Error> for soroban_sdk::Error {
             Error::TokensAlreadyLocked => soroban_sdk::Error::from_contract_error(2009),
             Error::LoanNotFullyFunded => soroban_sdk::Error::from_contract_error(2010),
             Error::LoanNotMatured => soroban_sdk::Error::from_contract_error(2011),
+            Error::AlreadyMigrated => soroban_sdk::Error::from_contract_error(2012),
+            Error::MigrationPending => soroban_sdk::Error::from_contract_error(2013),
         }
     }
 }
 
+const DAY_IN_SECONDS: u64 = 86_400;
+const DAY_IN_LEDGERS: u32 = 17280;
+const PERSISTENT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Layout of stored entries written by this code:
+/// 0. every borrow and loan in the `"borrows"` and `"loans"` instance maps,
+///    from before the version was stored;
+/// 1. one persistent entry per borrow and per loan under [`DataKey`].
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Most borrows and loans `migrate` moves per call.
+pub const MIGRATION_BATCH_SIZE: u32 = 100;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Borrow(u128),
+    Loan(u128),
+}
 
-pub fn str_to_address(e: &Env, s: &str) -> Address {
-    // Convert &str → soroban_sdk::String → Address
-    Address::from_string(&String::from_str(e, s))
+/// Privileged operations that go through the `rbac` timelock while it is active.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TimelockAction {
+    /// Role and timelock changes.
+    Rbac(RbacAction),
+    /// `upgrade` to the given wasm hash.
+    Upgrade(BytesN<32>),
+    /// `set_rwa_token(rwa_token)`.
+    SetRwaToken(Address),
+}
+
+impl Action for TimelockAction {
+    fn apply(self, e: &Env, admin: &Address) {
+        match self {
+            TimelockAction::Rbac(action) => action.apply(e, admin),
+            TimelockAction::Upgrade(new_wasm_hash) => LoanContract::install(e, new_wasm_hash),
+            TimelockAction::SetRwaToken(rwa_token) => LoanContract::apply_rwa_token(e, rwa_token),
+        }
+    }
 }
 
+pub type Borrow = (
+    Address, //Endereço da construtora
+    String,  //Nome da cosntrutora
+    String,  //Nome do Imovel
+    u64,     //Data Limite (timestamp do ledger)
+    u32,     //APY
+    i128,    //Preco por token
+    u128,    //Percentual restante
+);
+
+pub type Loan = (
+    u128,    //ID do borrow
+    Address, //Endereço construtora
+    Address, //Endereço client
+    u128,    //Dinheiro
+);
+
 #[contractimpl]
 impl LoanContract {
     /// Initialize the loan contract with admin and RWA token address
     pub fn initialize(e: Env, admin: Address, rwa_token_address: Address) {
-        e.storage().instance().set(&"admin", &admin);
+        rbac::initialize(&e, &admin);
         e.storage().instance().set(&"rwa_token", &rwa_token_address);
         e.storage().instance().set(&"next_borrow_id", &1u128);
         e.storage().instance().set(&"next_loan_id", &1u128);
+        e.storage()
+            .instance()
+            .set(&"schema_version", &SCHEMA_VERSION);
+    }
+
+    /// Admin-only. Timelocked. Replaces the contract's code with the uploaded
+    /// wasm `new_wasm_hash`, keeping its address, borrows and loans. Call
+    /// `migrate` next if the new code bumps [`SCHEMA_VERSION`].
+    pub fn upgrade(e: Env, admin: Address, new_wasm_hash: BytesN<32>) {
+        rbac::require_role(&e, &admin, Role::Admin);
+        rbac::timelock::require_unlocked(&e);
+        Self::install(&e, new_wasm_hash);
+    }
+
+    fn install(e: &Env, new_wasm_hash: BytesN<32>) {
+        e.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+        events::ContractUpgraded {
+            wasm_hash: new_wasm_hash,
+        }
+        .publish(e);
+    }
+
+    /// Admin-only. Brings stored entries up to [`SCHEMA_VERSION`]. Panics with
+    /// `AlreadyMigrated` if they already are.
+    ///
+    /// Moves at most `limit` borrows and loans per call out of the version 0
+    /// instance maps, so a long loan book fits in several transactions.
+    /// Returns how many are left to move, or 0 once the maps are gone and the
+    /// stored version has been bumped. Until then borrows and loans cannot be
+    /// created or read and fail with `MigrationPending`.
+    pub fn migrate(e: Env, admin: Address, limit: u32) -> u32 {
+        rbac::require_role(&e, &admin, Role::Admin);
+
+        let from = Self::schema_version(e.clone());
+        if from >= SCHEMA_VERSION {
+            panic_with_error!(&e, Error::AlreadyMigrated);
+        }
+
+        let mut budget = if limit == 0 {
+            MIGRATION_BATCH_SIZE
+        } else {
+            limit.min(MIGRATION_BATCH_SIZE)
+        };
+        let instance = e.storage().instance();
+        let mut borrows: Map<u128, Borrow> = instance.get(&"borrows").unwrap_or(Map::new(&e));
+        let mut loans: Map<u128, Loan> = instance.get(&"loans").unwrap_or(Map::new(&e));
+        for (id, borrow) in borrows.clone().iter().take(budget as usize) {
+            Self::write_borrow(&e, id, &borrow);
+            borrows.remove(id);
+            budget -= 1;
+        }
+        for (id, loan) in loans.clone().iter().take(budget as usize) {
+            Self::write_loan(&e, id, &loan);
+            loans.remove(id);
+        }
+
+        let left = borrows.len() + loans.len();
+        if left > 0 {
+            instance.set(&"borrows", &borrows);
+            instance.set(&"loans", &loans);
+            return left;
+        }
+
+        instance.remove(&"borrows");
+        instance.remove(&"loans");
+        instance.set(&"schema_version", &SCHEMA_VERSION);
+        events::SchemaMigrated {
+            from,
+            to: SCHEMA_VERSION,
+        }
+        .publish(&e);
+        0
+    }
+
+    pub fn schema_version(e: Env) -> u32 {
+        e.storage().instance().get(&"schema_version").unwrap_or(0)
     }
 
-    fn get_borrow_storage(env: &Env) -> Map<u128, 
-            (Address, //Endereço da construtora
-            String, //Nome da cosntrutora
-            String, //Nome do Imovel
-            String, //Data Limite
-            u32, //APY
-            i128, //Preco por token
-            u128)> {
-        let borrows: Map<u128, 
-            (Address, //Endereço da construtora
-            String, //Nome da cosntrutora
-            String, //Nome do Imovel
-            String, //Data Limite
-            u32, //APY
-            i128, //Preco por token
-            u128)>= //Percentual restante
-            e.storage().instance().get(&"borrows").unwrap_or(Map::new(&e));
-            borrows
-    }
-
-    fn get_loan_storage(env: &Env) -> Map<u128, //ID do loan
-        (u128, //ID do borrow
-        Address, //Endereço construtora
-        Address, //Endereço client
-        u128)> {
-        let loans: Map<u128, //ID do loan
-            (u128, //ID do borrow
-            Address, //Endereço construtora
-            Address, //Endereço client
-            u128)> = //Dinheiro
-            e.storage().instance().get(&"loans").unwrap_or(Map::new(&e));
-        loans
-        } 
-
-
-    fn adicionar_dias(dias: i64) -> String {
-        let hoje = Local::now();
-        let nova_data = hoje + Duration::days(dias);
-        
-        format!("{:02}/{:02}/{:04}", nova_data.day(), nova_data.month(), nova_data.year())
-    }
-
-    pub fn create_borrow(e: Env, builder: Address, property_id: u128, duration_days: u32, apy: u32) -> u128   {
-        let ecr1155_client = ecr1155::Client::new(&e, &str_to_address(&e, "CD7MWUED4MXNKY3ADSWJREYWK6AFRHH2ZNCEBIRN5UZBSZLFKZHYIRLH"));
+    /// Admin-only. Timelocked. Gives `role` to `account`.
+    pub fn grant_role(e: Env, admin: Address, role: Role, account: Address) {
+        rbac::grant_role(&e, &admin, role, &account);
+    }
+
+    /// Admin-only. Timelocked. Takes `role` away from `account`.
+    pub fn revoke_role(e: Env, admin: Address, role: Role, account: Address) {
+        rbac::revoke_role(&e, &admin, role, &account);
+    }
+
+    pub fn renounce_role(e: Env, account: Address, role: Role) {
+        rbac::renounce_role(&e, &account, role);
+    }
+
+    pub fn has_role(e: Env, role: Role, account: Address) -> bool {
+        rbac::has_role(&e, role, &account)
+    }
+
+    /// Admin-only. Offers `admin`'s role to `new_admin`, who must accept it.
+    /// The offer only becomes acceptable after the timelock's minimum delay.
+    pub fn propose_admin(e: Env, admin: Address, new_admin: Address) {
+        rbac::propose_admin(&e, &admin, &new_admin);
+    }
+
+    /// Completes a handover: `new_admin` becomes an admin in place of the
+    /// account that proposed it.
+    pub fn accept_admin(e: Env, new_admin: Address) {
+        rbac::accept_admin(&e, &new_admin);
+    }
+
+    pub fn pending_admin(e: Env) -> Option<Address> {
+        rbac::pending_admin(&e)
+    }
+
+    /// See [`rbac::timelock::set_min_delay`].
+    pub fn set_min_delay(e: Env, admin: Address, delay: u64) {
+        rbac::timelock::set_min_delay(&e, &admin, delay);
+    }
+
+    pub fn min_delay(e: Env) -> u64 {
+        rbac::timelock::min_delay(&e)
+    }
+
+    /// See [`rbac::timelock::schedule`]. Returns the operation id.
+    pub fn schedule_action(
+        e: Env,
+        admin: Address,
+        action: TimelockAction,
+        delay: u64,
+    ) -> BytesN<32> {
+        let (operation_id, eta) = rbac::timelock::schedule_action(&e, &admin, &action, delay);
+        events::ActionScheduled {
+            operation_id: operation_id.clone(),
+            action,
+            eta,
+        }
+        .publish(&e);
+        operation_id
+    }
+
+    /// See [`rbac::timelock::cancel`].
+    pub fn cancel_action(e: Env, admin: Address, operation_id: BytesN<32>) {
+        rbac::timelock::cancel(&e, &admin, &operation_id);
+    }
+
+    /// See [`rbac::timelock::execute`].
+    pub fn execute_action(e: Env, admin: Address, action: TimelockAction) {
+        rbac::timelock::execute_action(&e, &admin, action);
+    }
+
+    /// See [`rbac::timelock::eta`].
+    pub fn action_eta(e: Env, operation_id: BytesN<32>) -> Option<u64> {
+        rbac::timelock::eta(&e, &operation_id)
+    }
+
+    /// Admin-only. Timelocked. Points the contract at a different ecr1155
+    /// deployment.
+    pub fn set_rwa_token(e: Env, admin: Address, rwa_token_address: Address) {
+        rbac::require_role(&e, &admin, Role::Admin);
+        rbac::timelock::require_unlocked(&e);
+        Self::apply_rwa_token(&e, rwa_token_address);
+    }
+
+    fn apply_rwa_token(e: &Env, rwa_token_address: Address) {
+        e.storage().instance().set(&"rwa_token", &rwa_token_address);
+        events::RwaTokenSet {
+            rwa_token: rwa_token_address,
+        }
+        .publish(e);
+    }
+
+    fn require_migrated(e: &Env) {
+        if Self::schema_version(e.clone()) < SCHEMA_VERSION {
+            panic_with_error!(e, Error::MigrationPending);
+        }
+    }
+
+    fn extend_persistent(e: &Env, key: &DataKey) {
+        e.storage().persistent().extend_ttl(
+            key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn read_borrow(e: &Env, borrow_id: u128) -> Option<Borrow> {
+        let key = DataKey::Borrow(borrow_id);
+        let borrow = e.storage().persistent().get(&key);
+        if borrow.is_some() {
+            Self::extend_persistent(e, &key);
+        }
+        borrow
+    }
+
+    fn write_borrow(e: &Env, borrow_id: u128, borrow: &Borrow) {
+        let key = DataKey::Borrow(borrow_id);
+        e.storage().persistent().set(&key, borrow);
+        Self::extend_persistent(e, &key);
+    }
+
+    fn read_loan(e: &Env, loan_id: u128) -> Option<Loan> {
+        let key = DataKey::Loan(loan_id);
+        let loan = e.storage().persistent().get(&key);
+        if loan.is_some() {
+            Self::extend_persistent(e, &key);
+        }
+        loan
+    }
+
+    fn write_loan(e: &Env, loan_id: u128, loan: &Loan) {
+        let key = DataKey::Loan(loan_id);
+        e.storage().persistent().set(&key, loan);
+        Self::extend_persistent(e, &key);
+    }
+
+    fn rwa_token(e: &Env) -> RealEstateTokenClient<'_> {
+        let rwa_token_address: Address = e.storage().instance().get(&"rwa_token").unwrap();
+        RealEstateTokenClient::new(e, &rwa_token_address)
+    }
+
+    fn adicionar_dias(e: &Env, dias: u32) -> u64 {
+        e.ledger().timestamp() + dias as u64 * DAY_IN_SECONDS
+    }
+
+    pub fn create_borrow(
+        e: Env,
+        builder: Address,
+        property_id: u128,
+        duration_days: u32,
+        apy: u32,
+    ) -> u128 {
+        builder.require_auth();
+        Self::require_migrated(&e);
+
+        let ecr1155_client = Self::rwa_token(&e);
         let property = ecr1155_client.get_property(&property_id);
+        if property.builder != builder {
+            panic_with_error!(&e, Error::Unauthorized);
+        }
 
-        let mut borrows: Map<u128, 
-            (Address, //Endereço da construtora
-            String, //Nome da cosntrutora
-            String, //Nome do Imovel
-            String, //Data Limite
-            u32, //APY
-            i128, //Preco por token
-            u128)>= //Percentual restante
-            e.storage().instance().get(&"borrows").unwrap_or(Map::new(&e));
-        let updated_borrow_info = 
-            (builder, 
-            property.nome_construtora, 
-            property.name_property,
-            Self::adicionar_dias(duration_days),
+        let deadline = Self::adicionar_dias(&e, duration_days);
+        let price = ecr1155_client.price(&property);
+        let funding_bps = ecr1155_client.percentual(&property);
+        let updated_borrow_info = (
+            builder.clone(),
+            property.nome_construtora.clone(),
+            property.name_property.clone(),
+            deadline,
             apy,
-            property.price,
-            property.percentual
-            );
-        let borrow_id: u128 = e.storage().instance().get(&"next_borrow_id")
-        borrows.set(borrow_id, updated_borrow_info);
-        e.storage().instance().set(&"borrows", &borrows);
-        e.storage().instance().set(&"next_borrow_id", &(borrow_id + 1));
+            price,
+            funding_bps,
+        );
+        let borrow_id: u128 = e.storage().instance().get(&"next_borrow_id").unwrap();
+        Self::write_borrow(&e, borrow_id, &updated_borrow_info);
+        e.storage()
+            .instance()
+            .set(&"next_borrow_id", &(borrow_id + 1));
+
+        events::BorrowCreated {
+            borrow_id,
+            builder,
+            property_id,
+            deadline,
+            apy,
+            price,
+            funding_bps,
+        }
+        .publish(&e);
         borrow_id
     }
 
-    pub fn create_loan (e: Env, id_borrow: u128, builder : Address, investorAddress: Address, investment : u128) { //Assinatura do investidor, com o dinheiro dele
-        let ecr1155_client = ecr1155::Client::new(&e, &str_to_address(&e, "CD7MWUED4MXNKY3ADSWJREYWK6AFRHH2ZNCEBIRN5UZBSZLFKZHYIRLH"));
-        let property = ecr1155_client.get_property(&property_id);
+    pub fn create_loan(
+        e: Env,
+        id_borrow: u128,
+        builder: Address,
+        investor: Address,
+        investment: u128,
+    ) -> u128 {
+        //Assinatura do investidor, com o dinheiro dele
+        investor.require_auth();
+        Self::require_migrated(&e);
+
+        let borrow = Self::read_borrow(&e, id_borrow)
+            .unwrap_or_else(|| panic_with_error!(&e, Error::LoanNotFound));
+        if borrow.0 != builder {
+            panic_with_error!(&e, Error::Unauthorized);
+        }
 
-        let mut loans: Map<u128, //ID do loan
-        (u128, //ID do borrow
-        Address, //Endereço construtora
-        Address, //Endereço client
-        u128)> = //Dinheiro
-        e.storage().instance().get(&"loans").unwrap_or(Map::new(&e));
-
-        let update_loan_info = 
-        (id_borrow,
-        builder,
-        investorAddress,
-        investment
-        );
+        let update_loan_info = (id_borrow, builder.clone(), investor.clone(), investment);
 
-        let loan_id: u128 = e.storage().instance().get(&"next_loan_id")
-        loans.set(loan_id, update_loan_info);
-        e.storage().instance().set(&"loans", &loans);
+        let loan_id: u128 = e.storage().instance().get(&"next_loan_id").unwrap();
+        Self::write_loan(&e, loan_id, &update_loan_info);
         e.storage().instance().set(&"next_loan_id", &(loan_id + 1));
+
+        events::LoanCreated {
+            loan_id,
+            borrow_id: id_borrow,
+            builder,
+            investor,
+            investment,
+        }
+        .publish(&e);
         loan_id
     }
 
-    pub fn get_borrows(e: &Env) -> Map<u128, (Address, String, String, String, u32, i128, u128)> {
-        let hoje = Local::now().naive_local().date();
-        let mut borrows: Map<u128, (Address, String, String, String, u32, i128, u128)> = 
-            e.storage().instance().get(&"borrows").unwrap_or(Map::new(e));
-        
-        let mut borrows_filtrados = Map::new(e);
-        
-        for (id, borrow) in borrows.iter() {
-            let (builder, construtora_nome, imovel_nome, data_limite_str, apy, preco, percentual) = borrow;
-            
-            // Converter a string de data para NaiveDate
-            if let Ok(data_limite) = parse_data_string(&data_limite_str) {
-                if data_limite > hoje {
-                    borrows_filtrados.set(id, (builder, construtora_nome, imovel_nome, data_limite_str, apy, preco, percentual));
+    /// Borrows whose deadline has not passed yet.
+    pub fn get_borrows(e: Env) -> Map<u128, Borrow> {
+        Self::require_migrated(&e);
+        let hoje = e.ledger().timestamp();
+        let next_borrow_id: u128 = e.storage().instance().get(&"next_borrow_id").unwrap();
+
+        let mut borrows_filtrados = Map::new(&e);
+
+        for id in 1..next_borrow_id {
+            if let Some(borrow) = Self::read_borrow(&e, id) {
+                if borrow.3 > hoje {
+                    borrows_filtrados.set(id, borrow);
                 }
             }
         }
-        
+
         borrows_filtrados
     }
 
-    // Função auxiliar para converter string "DD/MM/AAAA" para NaiveDate
-    fn parse_data_string(data_str: &String) -> Result<NaiveDate, ()> {
-        let parts: Vec<&str> = data_str.split('/').collect();
-        if parts.len() != 3 {
-            return Err(());
-        }
-        
-        let dia = parts[0].parse::<u32>().map_err(|_| ())?;
-        let mes = parts[1].parse::<u32>().map_err(|_| ())?;
-        let ano = parts[2].parse::<i32>().map_err(|_| ())?;
-        
-        NaiveDate::from_ymd_opt(ano, mes, dia).ok_or(())
+    /// Loan `loan_id`. Panics with `LoanNotFound` if there is none.
+    pub fn get_loan(e: Env, loan_id: u128) -> Loan {
+        Self::require_migrated(&e);
+        Self::read_loan(&e, loan_id).unwrap_or_else(|| panic_with_error!(&e, Error::LoanNotFound))
     }
 
-    
-
-
-
-
-
     // Invest in a loan
     // pub fn invest(e: Env, investor: Address, loan_id: u128, amount: i128) {
     //     investor.require_auth();
@@ -485,4 +736,3 @@ impl LoanContract {
     //     investments.get((loan_id, investor)).unwrap_or(0)
     // }
 }
-
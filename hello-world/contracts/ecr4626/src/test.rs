@@ -1,21 +1,247 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{vec, Env, String};
+use ::ecr1155::{
+    testutils::upload_empty_wasm, RealEstateTokenContract, RealEstateTokenContractClient,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    BytesN, Env, IntoVal, String, Symbol,
+};
+
+struct Setup<'a> {
+    loans: LoanContractClient<'a>,
+    admin: Address,
+    builder: Address,
+    property_id: u128,
+}
+
+fn setup(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let builder = Address::generate(env);
+
+    let token_id = env.register(RealEstateTokenContract, ());
+    let token = RealEstateTokenContractClient::new(env, &token_id);
+    token.initialize(&admin);
+    token.add_builder(
+        &admin,
+        &builder,
+        &String::from_str(env, "Construtora Meridian"),
+        &BytesN::from_array(env, &[7; 32]),
+        &String::from_str(env, "ipfs://meridian-docs"),
+    );
+    let property_id = token.register_property(
+        &builder,
+        &String::from_str(env, "Edificio Aurora"),
+        &1_000_000,
+        &500_000,
+        &1_000,
+        &String::from_str(env, "Construtora Meridian"),
+        &String::from_str(env, "ipfs://aurora"),
+        &String::from_str(env, "AUR"),
+    );
+
+    let loans_id = env.register(LoanContract, ());
+    let loans = LoanContractClient::new(env, &loans_id);
+    loans.initialize(&admin, &token_id);
+
+    Setup {
+        loans,
+        admin,
+        builder,
+        property_id,
+    }
+}
 
 #[test]
-fn test() {
+fn test_create_borrow_and_loan() {
     let env = Env::default();
-    let contract_id = env.register(Contract, ());
-    let client = ContractClient::new(&env, &contract_id);
+    let s = setup(&env);
 
-    let words = client.hello(&String::from_str(&env, "Dev"));
+    let borrow_id = s.loans.create_borrow(&s.builder, &s.property_id, &30, &12);
+    assert_eq!(borrow_id, 1);
+
+    let borrows = s.loans.get_borrows();
+    let (builder, construtora, _, data_limite, apy, preco, _) = borrows.get(borrow_id).unwrap();
+    assert_eq!(builder, s.builder);
+    assert_eq!(construtora, String::from_str(&env, "Construtora Meridian"));
+    assert_eq!(data_limite, 30 * DAY_IN_SECONDS);
+    assert_eq!(apy, 12);
+    assert_eq!(preco, 1_000);
+
+    let investor = Address::generate(&env);
+    assert_eq!(
+        s.loans
+            .create_loan(&borrow_id, &s.builder, &investor, &10_000),
+        1
+    );
+    let (contract, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(contract, s.loans.address);
+    assert_eq!(
+        topics,
+        (
+            Symbol::new(&env, "loan_created"),
+            events::EVENT_VERSION,
+            1u128
+        )
+            .into_val(&env)
+    );
+    let event: events::LoanCreated = data.into_val(&env);
     assert_eq!(
-        words,
-        vec![
-            &env,
-            String::from_str(&env, "Hello"),
-            String::from_str(&env, "Dev"),
-        ]
+        event,
+        events::LoanCreated {
+            loan_id: 1,
+            borrow_id,
+            builder: s.builder.clone(),
+            investor,
+            investment: 10_000,
+        }
     );
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = 30 * DAY_IN_SECONDS + 1);
+    assert!(s.loans.get_borrows().is_empty());
+}
+
+#[test]
+fn test_create_borrow_requires_property_builder() {
+    let env = Env::default();
+    let s = setup(&env);
+
+    let stranger = Address::generate(&env);
+    let result = s
+        .loans
+        .try_create_borrow(&stranger, &s.property_id, &30, &12);
+    assert_eq!(result, Err(Ok(Error::Unauthorized.into())));
+}
+
+#[test]
+fn test_roles() {
+    let env = Env::default();
+    let s = setup(&env);
+    let operator = Address::generate(&env);
+
+    assert!(s.loans.has_role(&Role::Admin, &s.admin));
+    s.loans.grant_role(&s.admin, &Role::Operator, &operator);
+    assert!(s.loans.has_role(&Role::Operator, &operator));
+
+    let result = s.loans.try_set_rwa_token(&operator, &operator);
+    assert_eq!(result, Err(Ok(rbac::Error::MissingRole.into())));
+}
+
+#[test]
+fn test_upgrade_and_migrate() {
+    let env = Env::default();
+    let s = setup(&env);
+    let first = s.loans.create_borrow(&s.builder, &s.property_id, &30, &12);
+    let second = s.loans.create_borrow(&s.builder, &s.property_id, &60, &10);
+    let investor = Address::generate(&env);
+    s.loans.create_loan(&first, &s.builder, &investor, &10_000);
+    s.loans.create_loan(&first, &s.builder, &s.admin, &2_500);
+    s.loans.create_loan(&second, &s.builder, &investor, &7_000);
+
+    assert_eq!(s.loans.schema_version(), SCHEMA_VERSION);
+    let result = s.loans.try_migrate(&s.admin, &0);
+    assert_eq!(result, Err(Ok(Error::AlreadyMigrated.into())));
+
+    let borrows = s.loans.get_borrows();
+    let loans = [
+        s.loans.get_loan(&1),
+        s.loans.get_loan(&2),
+        s.loans.get_loan(&3),
+    ];
+
+    // Rewind to a deployment from before the version was stored, with every
+    // borrow and loan in the instance maps.
+    env.as_contract(&s.loans.address, || {
+        let mut borrow_map: Map<u128, Borrow> = Map::new(&env);
+        for (id, borrow) in borrows.iter() {
+            env.storage().persistent().remove(&DataKey::Borrow(id));
+            borrow_map.set(id, borrow);
+        }
+        let mut loan_map: Map<u128, Loan> = Map::new(&env);
+        for (id, loan) in (1..).zip(loans.iter()) {
+            env.storage().persistent().remove(&DataKey::Loan(id));
+            loan_map.set(id, loan.clone());
+        }
+        env.storage().instance().set(&"borrows", &borrow_map);
+        env.storage().instance().set(&"loans", &loan_map);
+        env.storage().instance().remove(&"schema_version");
+    });
+    assert_eq!(s.loans.schema_version(), 0);
+
+    let hash = upload_empty_wasm(&env);
+    let result = s.loans.try_upgrade(&s.builder, &hash);
+    assert_eq!(result, Err(Ok(rbac::Error::MissingRole.into())));
+    s.loans.upgrade(&s.admin, &hash);
+    // The empty module exports nothing, so the old entrypoints are gone.
+    assert!(s.loans.try_get_borrows().is_err());
+
+    // Stand-in for the new release: this crate's code at the same address,
+    // over the storage the upgrade left behind.
+    env.register_at(&s.loans.address, LoanContract, ());
+    let result = s.loans.try_get_loan(&1);
+    assert_eq!(result, Err(Ok(Error::MigrationPending.into())));
+    let result = s
+        .loans
+        .try_create_loan(&second, &s.builder, &investor, &1_000);
+    assert_eq!(result, Err(Ok(Error::MigrationPending.into())));
+
+    let result = s.loans.try_migrate(&s.builder, &0);
+    assert_eq!(result, Err(Ok(rbac::Error::MissingRole.into())));
+    assert_eq!(s.loans.migrate(&s.admin, &3), 2);
+    assert_eq!(s.loans.schema_version(), 0);
+    assert_eq!(s.loans.migrate(&s.admin, &3), 0);
+    assert_eq!(s.loans.schema_version(), SCHEMA_VERSION);
+    env.as_contract(&s.loans.address, || {
+        assert!(!env.storage().instance().has(&"borrows"));
+        assert!(!env.storage().instance().has(&"loans"));
+    });
+
+    assert_eq!(s.loans.get_borrows(), borrows);
+    assert_eq!(s.loans.get_loan(&1), loans[0]);
+    assert_eq!(s.loans.get_loan(&2), loans[1]);
+    assert_eq!(
+        s.loans.get_loan(&3),
+        (second, s.builder.clone(), investor.clone(), 7_000)
+    );
+    assert_eq!(
+        s.loans.create_loan(&second, &s.builder, &investor, &1_000),
+        4
+    );
+
+    // Deadlines survived: only the 60 day borrow is still listed after 30 days.
+    env.ledger()
+        .with_mut(|li| li.timestamp = 30 * DAY_IN_SECONDS);
+    let listed = s.loans.get_borrows();
+    assert_eq!(listed.keys(), soroban_sdk::vec![&env, second]);
+    assert_eq!(listed.get(second).unwrap().3, 60 * DAY_IN_SECONDS);
+}
+
+#[test]
+fn test_timelocked_actions() {
+    let env = Env::default();
+    let s = setup(&env);
+    let token = Address::generate(&env);
+
+    let result = s.loans.try_initialize(&s.admin, &token);
+    assert_eq!(result, Err(Ok(rbac::Error::AlreadyInitialized.into())));
+
+    s.loans.set_min_delay(&s.admin, &86_400);
+    let result = s.loans.try_set_rwa_token(&s.admin, &token);
+    assert_eq!(result, Err(Ok(rbac::Error::TimelockRequired.into())));
+    let hash = upload_empty_wasm(&env);
+    let result = s.loans.try_upgrade(&s.admin, &hash);
+    assert_eq!(result, Err(Ok(rbac::Error::TimelockRequired.into())));
+
+    let action = TimelockAction::SetRwaToken(token.clone());
+    s.loans.schedule_action(&s.admin, &action, &86_400);
+    env.ledger().with_mut(|li| li.timestamp = 86_400);
+    s.loans.execute_action(&s.admin, &action);
+    env.as_contract(&s.loans.address, || {
+        let rwa_token: Address = env.storage().instance().get(&"rwa_token").unwrap();
+        assert_eq!(rwa_token, token);
+    });
 }
@@ -0,0 +1,314 @@
+#![no_std]
+//! Role-based access control shared by the workspace contracts.
+//!
+//! This is a plain library, not a contract: each contract calls into it from
+//! its own entrypoints and the role assignments live in that contract's storage.
+//! Accounts holding [`Role::Admin`] grant and revoke every role, including
+//! `Admin` itself. The last `Admin` can never be removed, so a contract cannot
+//! lock itself out. Handing administration over to a new account takes two
+//! steps, [`propose_admin`] then [`accept_admin`], and privileged operations
+//! can be put behind a [`timelock`].
+use soroban_sdk::{contracttype, panic_with_error, Address, Env, Symbol};
+
+mod test;
+pub mod timelock;
+
+const DAY_IN_LEDGERS: u32 = 17280;
+const ROLE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const ROLE_LIFETIME_THRESHOLD: u32 = ROLE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    MissingRole,
+    LastAdmin,
+    AlreadyInitialized,
+    NoPendingAdmin,
+    TimelockRequired,
+    OperationNotFound,
+    OperationNotReady,
+    OperationExpired,
+    InvalidDelay,
+    OperationExists,
+}
+
+impl From<Error> for soroban_sdk::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::MissingRole => soroban_sdk::Error::from_contract_error(3001),
+            Error::LastAdmin => soroban_sdk::Error::from_contract_error(3002),
+            Error::AlreadyInitialized => soroban_sdk::Error::from_contract_error(3003),
+            Error::NoPendingAdmin => soroban_sdk::Error::from_contract_error(3004),
+            Error::TimelockRequired => soroban_sdk::Error::from_contract_error(3005),
+            Error::OperationNotFound => soroban_sdk::Error::from_contract_error(3006),
+            Error::OperationNotReady => soroban_sdk::Error::from_contract_error(3007),
+            Error::OperationExpired => soroban_sdk::Error::from_contract_error(3008),
+            Error::InvalidDelay => soroban_sdk::Error::from_contract_error(3009),
+            Error::OperationExists => soroban_sdk::Error::from_contract_error(3010),
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// Manages every other role and the contract's own configuration.
+    Admin,
+    /// Identity, KYC and transfer-restriction work.
+    Compliance,
+    /// May halt and resume the contract.
+    Pauser,
+    /// Pushes off-chain data such as prices or dates.
+    Oracle,
+    /// Day-to-day operational tasks delegated by the platform.
+    Operator,
+    /// Issues additional fractions of existing properties.
+    Minter,
+}
+
+/// Schema version of the role events, published as their second topic.
+pub const EVENT_VERSION: u32 = 1;
+
+/// `account` was given `role`. Published with the topics
+/// `("role_granted", EVENT_VERSION, role)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleGranted {
+    pub role: Role,
+    pub account: Address,
+    /// Admin who granted it; the admin itself on initialization.
+    pub admin: Address,
+}
+
+/// `account` lost `role`. Published with the topics
+/// `("role_revoked", EVENT_VERSION, role)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub account: Address,
+    /// Admin who revoked it, or `account` itself when renounced.
+    pub admin: Address,
+}
+
+/// An admin offered to hand administration over to `new_admin`. Published
+/// with the topics `("admin_proposed", EVENT_VERSION)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposed {
+    pub admin: Address,
+    pub new_admin: Address,
+    /// Ledger timestamp from which `new_admin` can accept.
+    pub eta: u64,
+}
+
+fn publish_granted(env: &Env, role: Role, account: &Address, admin: &Address) {
+    env.events().publish(
+        (Symbol::new(env, "role_granted"), EVENT_VERSION, role),
+        RoleGranted {
+            role,
+            account: account.clone(),
+            admin: admin.clone(),
+        },
+    );
+}
+
+fn publish_revoked(env: &Env, role: Role, account: &Address, admin: &Address) {
+    env.events().publish(
+        (Symbol::new(env, "role_revoked"), EVENT_VERSION, role),
+        RoleRevoked {
+            role,
+            account: account.clone(),
+            admin: admin.clone(),
+        },
+    );
+}
+
+/// Handover offered by `admin` to `new_admin`, acceptable from `eta` on.
+#[contracttype]
+#[derive(Clone)]
+struct PendingAdmin {
+    admin: Address,
+    new_admin: Address,
+    eta: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum RbacKey {
+    HasRole(Role, Address),
+    MemberCount(Role),
+    PendingAdmin,
+}
+
+/// Grants `Admin` to the first administrator. Panics if any admin already exists.
+pub fn initialize(env: &Env, admin: &Address) {
+    if member_count(env, Role::Admin) > 0 {
+        panic_with_error!(env, Error::AlreadyInitialized);
+    }
+    write_role(env, Role::Admin, admin, true);
+    publish_granted(env, Role::Admin, admin, admin);
+}
+
+pub fn has_role(env: &Env, role: Role, account: &Address) -> bool {
+    let key = RbacKey::HasRole(role, account.clone());
+    let granted = env.storage().persistent().has(&key);
+    if granted {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ROLE_LIFETIME_THRESHOLD, ROLE_BUMP_AMOUNT);
+    }
+    granted
+}
+
+/// Number of accounts currently holding `role`.
+pub fn member_count(env: &Env, role: Role) -> u32 {
+    env.storage()
+        .instance()
+        .get(&RbacKey::MemberCount(role))
+        .unwrap_or(0)
+}
+
+/// Requires `account`'s authorization and that it holds `role`.
+pub fn require_role(env: &Env, account: &Address, role: Role) {
+    account.require_auth();
+    if !has_role(env, role, account) {
+        panic_with_error!(env, Error::MissingRole);
+    }
+}
+
+/// Admin-only. Timelocked. Granting a role the account already holds is a
+/// no-op.
+pub fn grant_role(env: &Env, admin: &Address, role: Role, account: &Address) {
+    timelock::require_unlocked(env);
+    require_role(env, admin, Role::Admin);
+    apply_grant(env, admin, role, account);
+}
+
+/// [`grant_role`] for a grant `admin` has already been authorized for, such as
+/// an executed [`timelock::RbacAction::GrantRole`].
+pub fn apply_grant(env: &Env, admin: &Address, role: Role, account: &Address) {
+    if has_role(env, role, account) {
+        return;
+    }
+    write_role(env, role, account, true);
+    publish_granted(env, role, account, admin);
+}
+
+/// Admin-only. Timelocked, so one admin cannot instantly strip the others.
+/// Revoking a role the account does not hold is a no-op.
+pub fn revoke_role(env: &Env, admin: &Address, role: Role, account: &Address) {
+    timelock::require_unlocked(env);
+    require_role(env, admin, Role::Admin);
+    apply_revoke(env, admin, role, account);
+}
+
+/// [`revoke_role`] for a revocation `admin` has already been authorized for,
+/// such as an executed [`timelock::RbacAction::RevokeRole`].
+pub fn apply_revoke(env: &Env, admin: &Address, role: Role, account: &Address) {
+    if !has_role(env, role, account) {
+        return;
+    }
+    write_role(env, role, account, false);
+    publish_revoked(env, role, account, admin);
+}
+
+/// Lets `account` give up one of its own roles.
+pub fn renounce_role(env: &Env, account: &Address, role: Role) {
+    require_role(env, account, role);
+    write_role(env, role, account, false);
+    publish_revoked(env, role, account, account);
+}
+
+/// Admin-only. Offers `admin`'s role to `new_admin`, replacing any pending
+/// offer. The offer can be accepted once the timelock's minimum delay has
+/// passed, so a handover is never instant while the timelock is active.
+pub fn propose_admin(env: &Env, admin: &Address, new_admin: &Address) {
+    require_role(env, admin, Role::Admin);
+    let eta = env.ledger().timestamp() + timelock::min_delay(env);
+    env.storage().instance().set(
+        &RbacKey::PendingAdmin,
+        &PendingAdmin {
+            admin: admin.clone(),
+            new_admin: new_admin.clone(),
+            eta,
+        },
+    );
+    env.events().publish(
+        (Symbol::new(env, "admin_proposed"), EVENT_VERSION),
+        AdminProposed {
+            admin: admin.clone(),
+            new_admin: new_admin.clone(),
+            eta,
+        },
+    );
+}
+
+/// Completes the handover offered to `new_admin`: it becomes an admin and the
+/// proposer stops being one. An offer is withdrawn if its proposer loses the
+/// admin role before it is accepted.
+pub fn accept_admin(env: &Env, new_admin: &Address) {
+    new_admin.require_auth();
+    let pending: PendingAdmin = env
+        .storage()
+        .instance()
+        .get(&RbacKey::PendingAdmin)
+        .unwrap_or_else(|| panic_with_error!(env, Error::NoPendingAdmin));
+    if pending.new_admin != *new_admin {
+        panic_with_error!(env, Error::NoPendingAdmin);
+    }
+    if env.ledger().timestamp() < pending.eta {
+        panic_with_error!(env, Error::OperationNotReady);
+    }
+    env.storage().instance().remove(&RbacKey::PendingAdmin);
+
+    if !has_role(env, Role::Admin, new_admin) {
+        write_role(env, Role::Admin, new_admin, true);
+        publish_granted(env, Role::Admin, new_admin, &pending.admin);
+    }
+    if pending.admin != *new_admin && has_role(env, Role::Admin, &pending.admin) {
+        write_role(env, Role::Admin, &pending.admin, false);
+        publish_revoked(env, Role::Admin, &pending.admin, new_admin);
+    }
+}
+
+/// Account the current handover offer is addressed to, if any.
+pub fn pending_admin(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&RbacKey::PendingAdmin)
+        .map(|pending: PendingAdmin| pending.new_admin)
+}
+
+fn write_role(env: &Env, role: Role, account: &Address, granted: bool) {
+    let key = RbacKey::HasRole(role, account.clone());
+    let count = member_count(env, role);
+    if granted {
+        env.storage().persistent().set(&key, &());
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ROLE_LIFETIME_THRESHOLD, ROLE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .set(&RbacKey::MemberCount(role), &(count + 1));
+    } else {
+        if role == Role::Admin && count <= 1 {
+            panic_with_error!(env, Error::LastAdmin);
+        }
+        env.storage().persistent().remove(&key);
+        env.storage()
+            .instance()
+            .set(&RbacKey::MemberCount(role), &(count - 1));
+        if role == Role::Admin {
+            drop_offer_from(env, account);
+        }
+    }
+}
+
+/// Withdraws a pending handover proposed by `account`, which is no longer an
+/// admin and so has nothing left to hand over.
+fn drop_offer_from(env: &Env, account: &Address) {
+    let pending: Option<PendingAdmin> = env.storage().instance().get(&RbacKey::PendingAdmin);
+    if pending.is_some_and(|pending| pending.admin == *account) {
+        env.storage().instance().remove(&RbacKey::PendingAdmin);
+    }
+}
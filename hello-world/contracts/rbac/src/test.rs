@@ -0,0 +1,235 @@
+#![cfg(test)]
+
+use super::*;
+use crate::timelock::RbacAction;
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger},
+    BytesN, Env,
+};
+
+#[contract]
+struct HostContract;
+
+#[contractimpl]
+impl HostContract {}
+
+#[test]
+fn test_grant_revoke_renounce() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HostContract, ());
+
+    let admin = Address::generate(&env);
+    let officer = Address::generate(&env);
+
+    // Each call runs in its own frame, as separate contract invocations would.
+    let run = |f: &dyn Fn()| env.as_contract(&contract_id, f);
+
+    run(&|| initialize(&env, &admin));
+    run(&|| {
+        assert!(has_role(&env, Role::Admin, &admin));
+        assert!(!has_role(&env, Role::Compliance, &officer));
+    });
+
+    run(&|| grant_role(&env, &admin, Role::Compliance, &officer));
+    run(&|| {
+        assert!(has_role(&env, Role::Compliance, &officer));
+        assert_eq!(member_count(&env, Role::Compliance), 1);
+    });
+
+    run(&|| revoke_role(&env, &admin, Role::Compliance, &officer));
+    run(&|| assert!(!has_role(&env, Role::Compliance, &officer)));
+
+    run(&|| grant_role(&env, &admin, Role::Pauser, &officer));
+    run(&|| renounce_role(&env, &officer, Role::Pauser));
+    run(&|| {
+        assert!(!has_role(&env, Role::Pauser, &officer));
+        assert_eq!(member_count(&env, Role::Pauser), 0);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3001)")]
+fn test_grant_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HostContract, ());
+
+    let admin = Address::generate(&env);
+    let officer = Address::generate(&env);
+
+    env.as_contract(&contract_id, || initialize(&env, &admin));
+    env.as_contract(&contract_id, || {
+        grant_role(&env, &officer, Role::Compliance, &officer)
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3002)")]
+fn test_last_admin_cannot_leave() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HostContract, ());
+
+    let admin = Address::generate(&env);
+
+    env.as_contract(&contract_id, || initialize(&env, &admin));
+    env.as_contract(&contract_id, || renounce_role(&env, &admin, Role::Admin));
+}
+
+#[test]
+fn test_admin_handover_and_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HostContract, ());
+
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let id = BytesN::from_array(&env, &[1; 32]);
+    let run = |f: &dyn Fn()| env.as_contract(&contract_id, f);
+
+    run(&|| initialize(&env, &admin));
+    run(&|| timelock::require_unlocked(&env));
+    run(&|| timelock::set_min_delay(&env, &admin, 86_400));
+
+    // The handover waits out the timelock delay too.
+    run(&|| propose_admin(&env, &admin, &new_admin));
+    run(&|| assert_eq!(pending_admin(&env), Some(new_admin.clone())));
+    env.ledger().with_mut(|li| li.timestamp = 86_400);
+    run(&|| accept_admin(&env, &new_admin));
+    run(&|| {
+        assert!(has_role(&env, Role::Admin, &new_admin));
+        assert!(!has_role(&env, Role::Admin, &admin));
+        assert_eq!(pending_admin(&env), None);
+    });
+
+    run(&|| {
+        assert_eq!(
+            timelock::schedule(&env, &new_admin, &id, 86_400),
+            2 * 86_400
+        )
+    });
+    run(&|| timelock::cancel(&env, &new_admin, &id));
+    run(&|| assert_eq!(timelock::eta(&env, &id), None));
+    run(&|| {
+        timelock::schedule(&env, &new_admin, &id, 86_400);
+    });
+    env.ledger().with_mut(|li| li.timestamp = 2 * 86_400);
+    run(&|| timelock::execute(&env, &new_admin, &id));
+}
+
+#[test]
+fn test_timelocked_actions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HostContract, ());
+
+    let admin = Address::generate(&env);
+    let officer = Address::generate(&env);
+    let run = |f: &dyn Fn()| env.as_contract(&contract_id, f);
+
+    run(&|| initialize(&env, &admin));
+    run(&|| timelock::set_min_delay(&env, &admin, 86_400));
+
+    let grant = RbacAction::GrantRole(Role::Compliance, officer.clone());
+    let revoke = RbacAction::RevokeRole(Role::Admin, admin.clone());
+    let unlock = RbacAction::SetMinDelay(0);
+    run(&|| {
+        let (id, eta) = timelock::schedule_action(&env, &admin, &grant, 86_400);
+        assert_eq!(id, timelock::operation_id(&env, &grant));
+        assert_eq!(eta, 86_400);
+    });
+    run(&|| {
+        timelock::schedule_action(&env, &admin, &unlock, 86_400);
+    });
+    env.ledger().with_mut(|li| li.timestamp = 86_400);
+    run(&|| timelock::execute_action(&env, &admin, grant.clone()));
+    run(&|| timelock::execute_action(&env, &admin, unlock.clone()));
+    run(&|| {
+        assert!(has_role(&env, Role::Compliance, &officer));
+        assert_eq!(timelock::min_delay(&env), 0);
+    });
+    run(&|| grant_role(&env, &admin, Role::Admin, &officer));
+
+    run(&|| timelock::set_min_delay(&env, &officer, 86_400));
+    run(&|| {
+        timelock::schedule_action(&env, &officer, &revoke, 86_400);
+    });
+    env.ledger().with_mut(|li| li.timestamp = 2 * 86_400);
+    run(&|| timelock::execute_action(&env, &officer, revoke.clone()));
+    run(&|| assert!(!has_role(&env, Role::Admin, &admin)));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3005)")]
+fn test_revoke_requires_unlocked_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HostContract, ());
+
+    let admin = Address::generate(&env);
+    let other = Address::generate(&env);
+    let run = |f: &dyn Fn()| env.as_contract(&contract_id, f);
+
+    run(&|| initialize(&env, &admin));
+    run(&|| grant_role(&env, &admin, Role::Admin, &other));
+    run(&|| timelock::set_min_delay(&env, &admin, 86_400));
+    run(&|| revoke_role(&env, &admin, Role::Admin, &other));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3005)")]
+fn test_grant_requires_unlocked_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HostContract, ());
+
+    let admin = Address::generate(&env);
+    let officer = Address::generate(&env);
+    let run = |f: &dyn Fn()| env.as_contract(&contract_id, f);
+
+    run(&|| initialize(&env, &admin));
+    run(&|| timelock::set_min_delay(&env, &admin, 86_400));
+    run(&|| grant_role(&env, &admin, Role::Compliance, &officer));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3004)")]
+fn test_revoked_proposer_voids_handover() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HostContract, ());
+
+    let admin = Address::generate(&env);
+    let other = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let run = |f: &dyn Fn()| env.as_contract(&contract_id, f);
+
+    run(&|| initialize(&env, &admin));
+    run(&|| grant_role(&env, &admin, Role::Admin, &other));
+    run(&|| propose_admin(&env, &admin, &new_admin));
+    run(&|| revoke_role(&env, &other, Role::Admin, &admin));
+    run(&|| assert_eq!(pending_admin(&env), None));
+    run(&|| accept_admin(&env, &new_admin));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3007)")]
+fn test_timelocked_operation_not_ready() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HostContract, ());
+
+    let admin = Address::generate(&env);
+    let id = BytesN::from_array(&env, &[1; 32]);
+
+    env.as_contract(&contract_id, || initialize(&env, &admin));
+    env.as_contract(&contract_id, || {
+        timelock::set_min_delay(&env, &admin, 86_400)
+    });
+    env.as_contract(&contract_id, || {
+        timelock::schedule(&env, &admin, &id, 86_400);
+    });
+    env.as_contract(&contract_id, || timelock::execute(&env, &admin, &id));
+}
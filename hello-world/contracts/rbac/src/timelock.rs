@@ -0,0 +1,258 @@
+//! Delayed execution of privileged operations.
+//!
+//! Once an admin sets a non-zero minimum delay, the operations a contract
+//! guards with [`require_unlocked`] can no longer be called directly. Instead
+//! an admin schedules the operation, identified by a hash of its parameters
+//! chosen by the contract, and executes it once the delay has passed. Until
+//! then any admin can cancel it, and anyone watching the events has that long
+//! to react. An operation not executed within [`GRACE_PERIOD`] of becoming
+//! ready expires.
+//!
+//! A contract lists the operations it guards in one `#[contracttype]` enum
+//! implementing [`Action`], with a variant wrapping [`RbacAction`] for the
+//! ones every contract has. Its entrypoints hand that enum to
+//! [`schedule_action`], [`cancel`], [`execute_action`] and [`eta`]. Each
+//! guarded entrypoint calls [`require_unlocked`] and says "Timelocked." in
+//! its docs.
+use soroban_sdk::{
+    contracttype, panic_with_error, xdr::ToXdr, Address, BytesN, Env, IntoVal, Symbol, Val,
+};
+
+use crate::{apply_grant, apply_revoke, require_role, Error, Role, DAY_IN_LEDGERS, EVENT_VERSION};
+
+/// How long a ready operation stays executable.
+pub const GRACE_PERIOD: u64 = 14 * 86_400;
+/// Longest minimum delay that can be set, so a contract cannot lock itself out.
+pub const MAX_DELAY: u64 = 30 * 86_400;
+/// Keeps queued operations live past their delay and grace period.
+const OPERATION_BUMP_AMOUNT: u32 = 45 * DAY_IN_LEDGERS;
+
+/// An operation was queued. Published with the topics
+/// `("operation_scheduled", EVENT_VERSION, id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperationScheduled {
+    pub id: BytesN<32>,
+    pub admin: Address,
+    /// Ledger timestamp from which it can be executed.
+    pub eta: u64,
+}
+
+/// A queued operation was dropped. Published with the topics
+/// `("operation_cancelled", EVENT_VERSION, id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperationCancelled {
+    pub id: BytesN<32>,
+    pub admin: Address,
+}
+
+/// A queued operation ran. Published with the topics
+/// `("operation_executed", EVENT_VERSION, id)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperationExecuted {
+    pub id: BytesN<32>,
+    pub admin: Address,
+}
+
+/// The minimum delay changed. Published with the topics
+/// `("min_delay_set", EVENT_VERSION)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MinDelaySet {
+    pub delay: u64,
+    pub admin: Address,
+}
+
+/// Operations of this crate that contracts put behind the timelock.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RbacAction {
+    /// [`grant_role(role, account)`](crate::grant_role).
+    GrantRole(Role, Address),
+    /// [`revoke_role(role, account)`](crate::revoke_role).
+    RevokeRole(Role, Address),
+    /// Changes the timelock's own minimum delay.
+    SetMinDelay(u64),
+}
+
+/// A contract's timelocked operations. The value is hashed into its operation
+/// id, so the same value must be scheduled and executed.
+pub trait Action: Clone + IntoVal<Env, Val> {
+    /// Performs the operation for `admin`, who has already been authorized.
+    fn apply(self, env: &Env, admin: &Address);
+}
+
+impl Action for RbacAction {
+    fn apply(self, env: &Env, admin: &Address) {
+        match self {
+            RbacAction::GrantRole(role, account) => apply_grant(env, admin, role, &account),
+            RbacAction::RevokeRole(role, account) => apply_revoke(env, admin, role, &account),
+            RbacAction::SetMinDelay(delay) => apply_min_delay(env, admin, delay),
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum TimelockKey {
+    MinDelay,
+    Operation(BytesN<32>),
+}
+
+/// Seconds every scheduled operation must wait. Zero disables the timelock.
+pub fn min_delay(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&TimelockKey::MinDelay)
+        .unwrap_or(0)
+}
+
+/// Panics with `TimelockRequired` while the timelock is active, for operations
+/// that must then go through [`schedule`] and [`execute`].
+pub fn require_unlocked(env: &Env) {
+    if min_delay(env) > 0 {
+        panic_with_error!(env, Error::TimelockRequired);
+    }
+}
+
+/// Admin-only. Turns the timelock on by setting its minimum delay in seconds.
+/// Timelocked: once on, the delay only changes through a scheduled
+/// [`RbacAction::SetMinDelay`].
+pub fn set_min_delay(env: &Env, admin: &Address, delay: u64) {
+    require_unlocked(env);
+    require_role(env, admin, Role::Admin);
+    apply_min_delay(env, admin, delay);
+}
+
+/// [`set_min_delay`] once `admin` has been authorized, as when executing a
+/// scheduled change of the delay.
+pub fn apply_min_delay(env: &Env, admin: &Address, delay: u64) {
+    if delay > MAX_DELAY {
+        panic_with_error!(env, Error::InvalidDelay);
+    }
+    env.storage().instance().set(&TimelockKey::MinDelay, &delay);
+    env.events().publish(
+        (Symbol::new(env, "min_delay_set"), EVENT_VERSION),
+        MinDelaySet {
+            delay,
+            admin: admin.clone(),
+        },
+    );
+}
+
+/// Admin-only. Queues operation `id` to become executable after `delay`
+/// seconds, which must be at least [`min_delay`]. Returns when it will be.
+pub fn schedule(env: &Env, admin: &Address, id: &BytesN<32>, delay: u64) -> u64 {
+    require_role(env, admin, Role::Admin);
+    if delay < min_delay(env) || delay > MAX_DELAY {
+        panic_with_error!(env, Error::InvalidDelay);
+    }
+    let key = TimelockKey::Operation(id.clone());
+    if env.storage().persistent().has(&key) {
+        panic_with_error!(env, Error::OperationExists);
+    }
+
+    let eta = env.ledger().timestamp() + delay;
+    env.storage().persistent().set(&key, &eta);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, OPERATION_BUMP_AMOUNT, OPERATION_BUMP_AMOUNT);
+    env.events().publish(
+        (
+            Symbol::new(env, "operation_scheduled"),
+            EVENT_VERSION,
+            id.clone(),
+        ),
+        OperationScheduled {
+            id: id.clone(),
+            admin: admin.clone(),
+            eta,
+        },
+    );
+    eta
+}
+
+/// Admin-only. Drops queued operation `id`.
+pub fn cancel(env: &Env, admin: &Address, id: &BytesN<32>) {
+    require_role(env, admin, Role::Admin);
+    let key = TimelockKey::Operation(id.clone());
+    if !env.storage().persistent().has(&key) {
+        panic_with_error!(env, Error::OperationNotFound);
+    }
+    env.storage().persistent().remove(&key);
+    env.events().publish(
+        (
+            Symbol::new(env, "operation_cancelled"),
+            EVENT_VERSION,
+            id.clone(),
+        ),
+        OperationCancelled {
+            id: id.clone(),
+            admin: admin.clone(),
+        },
+    );
+}
+
+/// Admin-only. Marks queued operation `id` as executed, panicking unless it is
+/// ready and has not expired. The caller then performs the operation.
+pub fn execute(env: &Env, admin: &Address, id: &BytesN<32>) {
+    require_role(env, admin, Role::Admin);
+    let key = TimelockKey::Operation(id.clone());
+    let eta: u64 = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| panic_with_error!(env, Error::OperationNotFound));
+    let now = env.ledger().timestamp();
+    if now < eta {
+        panic_with_error!(env, Error::OperationNotReady);
+    }
+    if now > eta + GRACE_PERIOD {
+        panic_with_error!(env, Error::OperationExpired);
+    }
+    env.storage().persistent().remove(&key);
+    env.events().publish(
+        (
+            Symbol::new(env, "operation_executed"),
+            EVENT_VERSION,
+            id.clone(),
+        ),
+        OperationExecuted {
+            id: id.clone(),
+            admin: admin.clone(),
+        },
+    );
+}
+
+/// When queued operation `id` becomes executable, if it is queued.
+pub fn eta(env: &Env, id: &BytesN<32>) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&TimelockKey::Operation(id.clone()))
+}
+
+/// Identifies `action` in the queue.
+pub fn operation_id<A: Action>(env: &Env, action: &A) -> BytesN<32> {
+    env.crypto().sha256(&action.clone().to_xdr(env)).to_bytes()
+}
+
+/// Admin-only. [`schedule`]s `action`, returning its operation id and when it
+/// becomes executable.
+pub fn schedule_action<A: Action>(
+    env: &Env,
+    admin: &Address,
+    action: &A,
+    delay: u64,
+) -> (BytesN<32>, u64) {
+    let id = operation_id(env, action);
+    let eta = schedule(env, admin, &id, delay);
+    (id, eta)
+}
+
+/// Admin-only. [`execute`]s the queued `action`, then performs it.
+pub fn execute_action<A: Action>(env: &Env, admin: &Address, action: A) {
+    execute(env, admin, &operation_id(env, &action));
+    action.apply(env, admin);
+}